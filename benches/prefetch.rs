@@ -0,0 +1,44 @@
+//! Overlap of window reading and inference with `PrefetchWindows`
+//!
+//! Reading and inference are simulated with fixed delays so the benchmark
+//! measures the pipelining itself: sequential processing costs the sum of
+//! both per window, prefetching approaches the larger of the two.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ndarray::Array1;
+use silero_vad_rs::utils::PrefetchWindows;
+use std::thread;
+use std::time::Duration;
+
+const WINDOWS: usize = 50;
+const READ_DELAY: Duration = Duration::from_micros(200);
+const INFERENCE_DELAY: Duration = Duration::from_micros(200);
+
+/// Windows from a slow disk
+fn slow_source() -> impl Iterator<Item = Array1<f32>> + Send + 'static {
+    (0..WINDOWS).map(|_| {
+        thread::sleep(READ_DELAY);
+        Array1::zeros(512)
+    })
+}
+
+fn infer(window: &Array1<f32>) -> f32 {
+    thread::sleep(INFERENCE_DELAY);
+    window.sum()
+}
+
+fn bench_prefetch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stream_from_disk");
+    group.bench_function("sequential", |b| {
+        b.iter(|| slow_source().map(|w| infer(&w)).sum::<f32>())
+    });
+    for depth in [1, 4] {
+        group.bench_function(format!("prefetch_depth_{}", depth), |b| {
+            b.iter(|| PrefetchWindows::new(slow_source(), depth).map(|w| infer(&w)).sum::<f32>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_prefetch);
+criterion_main!(benches);
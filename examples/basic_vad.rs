@@ -25,7 +25,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         16000, // sampling rate
         100,   // min silence duration (ms)
         30,    // speech pad (ms)
-    );
+    )?;
 
     // Check if input file exists
     let input_path = Path::new("examples/input.wav");
@@ -62,7 +62,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("Saving speech segments...");
         timestamps.par_iter().enumerate().for_each(|(i, ts)| {
             let output_path = format!("examples/speech_{}.wav", i+1);
-            let speech_audio = silero_vad_rs::utils::collect_chunks(&[ts.clone()], &audio, 16000).unwrap();
+            let speech_audio = silero_vad_rs::utils::collect_chunks(std::slice::from_ref(ts), &audio, 16000).unwrap();
             save_audio(&output_path, &speech_audio, 16000).unwrap();
             println!("  Saved segment {} to {}", i+1, output_path);
         });
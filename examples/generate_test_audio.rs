@@ -20,7 +20,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create speech segments at 1-2s and 3-4s
     for i in 0..total_samples {
         let t = i as f32 / samples_per_second as f32;
-        let sample = if (t >= 1.0 && t < 2.0) || (t >= 3.0 && t < 4.0) {
+        let sample = if (1.0..2.0).contains(&t) || (3.0..4.0).contains(&t) {
             // Speech segment - generate a simple sine wave
             0.5 * (2.0 * PI * 440.0 * t).sin()
         } else {
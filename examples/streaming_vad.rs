@@ -21,7 +21,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         16000, // sampling rate
         100,   // min silence duration (ms)
         30,    // speech pad (ms)
-    );
+    )?;
 
     println!("Starting streaming VAD simulation...");
     println!("This example simulates processing audio chunks in real-time");
@@ -29,7 +29,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Simulate processing audio chunks
     let chunk_size = 512; // for 16kHz
-    let mut current_time = 0.0;
     let time_per_chunk = chunk_size as f32 / 16000.0;
     
     // Simulate some audio chunks with speech and silence
@@ -39,10 +38,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let mut audio_chunk = Array1::zeros(chunk_size);
         
         // Simulate speech in some chunks
-        if (i >= 10 && i < 20) || (i >= 40 && i < 50) {
+        if (10..20).contains(&i) || (40..50).contains(&i) {
             // Add some signal to simulate speech
-            for j in 0..chunk_size {
-                audio_chunk[j] = 0.1 * (j as f32 / 10.0).sin();
+            for (j, sample) in audio_chunk.iter_mut().enumerate() {
+                *sample = 0.1 * (j as f32 / 10.0).sin();
             }
         }
         
@@ -51,9 +50,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Speech detected from {:.2}s to {:.2}s", ts.start, ts.end);
         }
         
-        // Update time
-        current_time += time_per_chunk;
-        
         // Simulate real-time processing delay
         thread::sleep(Duration::from_millis((time_per_chunk * 1000.0) as u64));
     }
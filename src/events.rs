@@ -0,0 +1,63 @@
+//! Structured library events
+//! 
+//! Key lifecycle and detection events are emitted through this module. With the
+//! `tracing` feature enabled they are recorded as `tracing` events carrying
+//! machine-readable fields under the `silero_vad` target; otherwise they are
+//! written as plain log lines through the `log` crate.
+
+use crate::vad::SpeechTimestamps;
+
+/// A model session was created
+pub(crate) fn model_loaded(source: &str, provider: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(target: "silero_vad", event = "model_loaded", source, provider);
+    #[cfg(not(feature = "tracing"))]
+    log::info!("Model loaded from {} running on {}", source, provider);
+}
+
+/// Execution providers were registered for a new session
+pub(crate) fn providers_registered(providers: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(target: "silero_vad", event = "providers_registered", providers);
+    #[cfg(not(feature = "tracing"))]
+    log::info!("Registered execution providers: {}", providers);
+}
+
+/// A model download attempt failed and will be retried
+pub(crate) fn download_retry(url: &str, attempt: u32, attempts: u32, error: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(target: "silero_vad", event = "download_retry", url, attempt, attempts, error);
+    #[cfg(not(feature = "tracing"))]
+    log::warn!("Download attempt {}/{} from {} failed: {}", attempt, attempts, url, error);
+}
+
+/// A speech segment was finalized
+pub(crate) fn segment_detected(ts: &SpeechTimestamps) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        target: "silero_vad",
+        event = "segment_detected",
+        start = ts.start,
+        end = ts.end,
+        confidence = ts.confidence
+    );
+    #[cfg(not(feature = "tracing"))]
+    log::debug!(
+        "Speech segment detected: {}s - {}s (confidence {:.2})",
+        crate::utils::Precision::default().format(ts.start),
+        crate::utils::Precision::default().format(ts.end),
+        ts.confidence
+    );
+}
+
+/// A streamed chunk did not start where the previous one ended
+pub(crate) fn stream_discontinuity(expected: usize, actual: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(target: "silero_vad", event = "stream_discontinuity", expected, actual);
+    #[cfg(not(feature = "tracing"))]
+    log::warn!(
+        "Discontinuous audio stream: chunk starts at sample {} but {} samples were processed",
+        actual,
+        expected
+    );
+}
@@ -14,16 +14,18 @@
 //! - Automatic model downloading from Silero repository
 //! - Multiple language support (English, Russian, German, Spanish)
 //! - Comprehensive error handling
+//! - Structured events via the optional `tracing` feature
 //! 
 //! # Example
 //! 
-//! ```rust
-//! use silero_vad::{SileroVAD, VADIterator};
-//! use silero_vad::utils::{read_audio, save_audio};
+//! ```rust,no_run
+//! use silero_vad_rs::{SileroVAD, VADIterator};
+//! use silero_vad_rs::utils::{read_audio, save_audio};
+//! use std::path::Path;
 //! 
 //! fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     // Load the model
-//!     let model = SileroVAD::new("path/to/silero_vad.onnx")?;
+//!     let model = SileroVAD::new(Path::new("path/to/silero_vad.onnx"))?;
 //!     
 //!     // Create a VAD iterator
 //!     let mut vad = VADIterator::new(
@@ -32,7 +34,7 @@
 //!         16000, // sampling rate
 //!         100,   // min silence duration (ms)
 //!         30,    // speech pad (ms)
-//!     );
+//!     )?;
 //! 
 //!     // Read audio file
 //!     let audio = read_audio("input.wav", 16000)?;
@@ -55,12 +57,24 @@
 //! }
 //! ```
 
+mod events;
 pub mod model;
+pub mod multichannel;
 pub mod utils;
 pub mod vad;
 
-pub use model::SileroVAD;
-pub use vad::{VADIterator, SpeechTimestamps};
+pub use model::{
+    download_model, ModelConfig, ModelMetadata, OutputTransform, Provider, SileroVAD,
+    TimingStats, SUPPORTED_SAMPLING_RATES,
+};
+pub use multichannel::MultiChannelVad;
+pub use vad::{
+    endpoint_from_probs, events_from_probs, segment_from_analysis, segment_from_bidirectional,
+    segment_from_offset_passes, segment_probabilities, stats_from_probs,
+    suggest_parameters_from_probs, window_from_ms, ClipClass, DetectionDetail, EndpointState,
+    ProbabilityAnalysis, VADIterator, SegmentWithAudio, SpeechTimestamps, SplitReason,
+    SuggestedParameters, VadConfig, VadEvent, VadResult, VadState, VadStats,
+};
 
 /// Supported languages for VAD
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -75,6 +89,23 @@ pub enum Language {
     Spanish,
 }
 
+/// Model URL used for English, which is also the default model
+pub(crate) const ENGLISH_MODEL_URL: &str = "https://models.silero.ai/models/en/en_v6_xlarge.onnx";
+
+impl Language {
+    /// Get the download URL of the ONNX model for this language
+    /// 
+    /// Returns `None` if no ONNX model is published for the language.
+    pub fn model_url(&self) -> Option<&'static str> {
+        match self {
+            Language::English => Some(ENGLISH_MODEL_URL),
+            Language::German => Some("https://models.silero.ai/models/de/de_v1.onnx"),
+            Language::Spanish => Some("https://models.silero.ai/models/es/es_v1.onnx"),
+            Language::Russian => None,
+        }
+    }
+}
+
 /// Error types for the Silero VAD library
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -87,6 +118,9 @@ pub enum Error {
     /// Error during audio processing
     #[error("Audio processing error: {0}")]
     AudioProcessing(String),
+    /// Model download failed after all retry attempts
+    #[error("Download error: {0}")]
+    Download(String),
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -3,18 +3,190 @@
 //! This module provides the core Silero VAD model implementation using the ONNX runtime.
 //! It supports both single chunk and batch processing of audio data.
 
-use crate::{Error, Result};
-use ndarray::{Array1, Array2, ArrayView1};
-use std::path::Path;
+use crate::{events, Error, Language, Result};
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, Axis};
+use std::path::{Path, PathBuf};
 use ort::{
-    execution_providers::{TensorRTExecutionProvider, CUDAExecutionProvider},
-    session::{Session, builder::GraphOptimizationLevel},
-    value::Tensor,
+    execution_providers::{
+        CoreMLExecutionProvider, CUDAExecutionProvider, DirectMLExecutionProvider, ExecutionProvider,
+        ExecutionProviderDispatch, ROCmExecutionProvider, TensorRTExecutionProvider,
+    },
+    session::{Session, builder::{GraphOptimizationLevel, SessionBuilder}},
+    tensor::TensorElementType,
+    value::{DynValue, Tensor},
 };
 use log::{info, debug};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
 
-const MODEL_URL: &str = "https://models.silero.ai/models/en/en_v6_xlarge.onnx";
+const MODEL_URL: &str = crate::ENGLISH_MODEL_URL;
+
+/// Execution providers for ONNX Runtime inference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Provider {
+    /// NVIDIA TensorRT
+    TensorRT,
+    /// NVIDIA CUDA
+    Cuda,
+    /// DirectML on Windows
+    DirectML,
+    /// CoreML on macOS and iOS
+    CoreML,
+    /// AMD ROCm
+    ROCm,
+    /// Default CPU provider, always available
+    Cpu,
+}
+
+/// Transform applied to the raw model output before it is used as a probability
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutputTransform {
+    /// Use the output as-is, for models that output probabilities
+    #[default]
+    Identity,
+    /// Map logits to probabilities with the logistic sigmoid
+    Sigmoid,
+}
+
+impl OutputTransform {
+    /// Apply the transform to a single output value
+    pub fn apply(self, value: f32) -> f32 {
+        match self {
+            OutputTransform::Identity => value,
+            OutputTransform::Sigmoid => 1.0 / (1.0 + (-value).exp()),
+        }
+    }
+}
+
+/// Model loading configuration
+/// 
+/// Controls where the model is downloaded from when it is not available
+/// locally, how transient download failures are retried, and how inference
+/// threads are scheduled.
+#[derive(Debug, Clone)]
+pub struct ModelConfig {
+    /// URL to download the model from when the local file doesn't exist
+    pub model_url: String,
+    /// Total number of download attempts (at least one attempt is always made)
+    pub download_attempts: u32,
+    /// Delay before the first retry, doubled after each failed attempt
+    pub retry_base_delay: Duration,
+    /// Number of threads used to parallelize a single inference call
+    pub intra_threads: usize,
+    /// Logical processors each intra-op worker thread may run on
+    /// 
+    /// ONNX Runtime runs inference on the calling thread plus
+    /// `intra_threads - 1` worker threads, so when set this must hold exactly
+    /// `intra_threads - 1` entries, each a non-empty list of 1-based logical
+    /// processor ids. Pinning workers away from the audio callback's core
+    /// reduces jitter in real-time servers. Affinity is honoured on Windows
+    /// and Linux; other platforms ignore it. Empty leaves scheduling to the OS.
+    pub thread_affinity: Vec<Vec<usize>>,
+    /// Execution providers to register, in order of preference
+    /// 
+    /// The first provider available on the machine runs inference, and the
+    /// CPU is always the final fallback, so it need not be listed. If listed,
+    /// [`Provider::Cpu`] must come last. Defaults to TensorRT, then CUDA.
+    pub execution_providers: Vec<Provider>,
+    /// Record the duration of every inference call, see [`SileroVAD::timing_stats`]
+    /// 
+    /// Off by default so the inference path takes no timestamps.
+    pub record_timings: bool,
+    /// Transform turning the model output into probabilities
+    /// 
+    /// Set [`OutputTransform::Sigmoid`] for model variants that output raw
+    /// logits, so thresholding against `[0, 1]` keeps working. Applies to
+    /// every inference path, single chunk and batched.
+    pub output_transform: OutputTransform,
+    /// Expected SHA-256 digest of a downloaded model, as a hex string
+    /// 
+    /// When set, a download is only put in place if its digest matches.
+    /// Otherwise a download is checked against the size announced by the
+    /// server or, if the server announces none, by loading it as a model.
+    pub model_sha256: Option<String>,
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        Self {
+            model_url: MODEL_URL.to_string(),
+            download_attempts: 3,
+            retry_base_delay: Duration::from_millis(500),
+            intra_threads: 1,
+            thread_affinity: Vec::new(),
+            execution_providers: vec![Provider::TensorRT, Provider::Cuda],
+            record_timings: false,
+            output_transform: OutputTransform::Identity,
+            model_sha256: None,
+        }
+    }
+}
+
+/// Summary of inference call durations
+/// 
+/// Each sample is one `session.run` call, which covers a whole batch when
+/// batch processing is used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingStats {
+    /// Number of recorded calls
+    pub count: usize,
+    /// Fastest call
+    pub min: Duration,
+    /// Average call duration
+    pub mean: Duration,
+    /// Slowest call
+    pub max: Duration,
+    /// 99th percentile (nearest rank) of the call durations
+    pub p99: Duration,
+}
+
+impl TimingStats {
+    /// Summarize a set of call durations
+    /// 
+    /// # Arguments
+    /// 
+    /// * `durations` - Recorded durations, in any order
+    /// 
+    /// # Returns
+    /// 
+    /// The summary, or `None` if no durations were recorded
+    pub fn from_durations(durations: &[Duration]) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+        let mut sorted = durations.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len();
+        let rank = (count * 99).div_ceil(100).max(1);
+        Some(Self {
+            count,
+            min: sorted[0],
+            mean: sorted.iter().sum::<Duration>() / count as u32,
+            max: sorted[count - 1],
+            p99: sorted[rank - 1],
+        })
+    }
+}
+
+/// Metadata embedded in the ONNX model
+/// 
+/// Fields the model doesn't carry are left empty (or 0 for the version).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelMetadata {
+    /// Graph name
+    pub name: String,
+    /// Name of the tool that produced the model
+    pub producer: String,
+    /// Model description
+    pub description: String,
+    /// Model version
+    pub version: i64,
+    /// Custom metadata entries
+    pub custom: BTreeMap<String, String>,
+}
 
 /// Main Silero VAD model wrapper
 /// 
@@ -23,19 +195,28 @@ const MODEL_URL: &str = "https://models.silero.ai/models/en/en_v6_xlarge.onnx";
 /// 
 /// # Example
 /// 
-/// ```rust
-/// use silero_vad::SileroVAD;
+/// ```rust,no_run
+/// use silero_vad_rs::SileroVAD;
 /// use ndarray::Array1;
+/// use std::path::Path;
 /// 
-/// let model = SileroVAD::new("path/to/model.onnx")?;
+/// # fn main() -> silero_vad_rs::Result<()> {
+/// let mut model = SileroVAD::new(Path::new("path/to/model.onnx"))?;
 /// let audio_chunk = Array1::zeros(512); // 512 samples for 16kHz
 /// let speech_prob = model.process_chunk(&audio_chunk.view(), 16000)?;
+/// # Ok(())
+/// # }
 /// ```
 pub struct SileroVAD {
     session: Session,
+    config: ModelConfig,
+    model_path: PathBuf,
+    provider: Provider,
     context: Array2<f32>,
     last_sr: u32,
     last_batch_size: usize,
+    timings: Vec<Duration>,
+    window_time: Option<Duration>,
 }
 
 impl SileroVAD {
@@ -44,7 +225,7 @@ impl SileroVAD {
     /// # Arguments
     /// 
     /// * `model_path` - Path to the ONNX model file. If the file doesn't exist,
-    ///                  it will be downloaded from the Silero model repository.
+    ///   it will be downloaded from the Silero model repository.
     /// 
     /// # Returns
     /// 
@@ -57,50 +238,297 @@ impl SileroVAD {
     /// * The model is invalid or incompatible
     /// * GPU initialization fails (falls back to CPU)
     pub fn new(model_path: &Path) -> Result<Self> {
+        Self::with_config(model_path, ModelConfig::default())
+    }
+
+    /// Create a new Silero VAD model with an explicit loading configuration
+    /// 
+    /// # Arguments
+    /// 
+    /// * `model_path` - Path to the ONNX model file. If the file doesn't exist,
+    ///   it will be downloaded from `config.model_url` and saved there, see
+    ///   [`download_model`].
+    /// * `config` - Model loading configuration
+    /// 
+    /// # Returns
+    /// 
+    /// A new `SileroVAD` instance ready for inference
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The model file cannot be loaded
+    /// * The model cannot be downloaded after all attempts (`Error::Download`)
+    /// * The model is invalid or incompatible
+    pub fn with_config(model_path: &Path, config: ModelConfig) -> Result<Self> {
         // Create models directory if it doesn't exist
         if let Some(parent) = model_path.parent() {
             fs::create_dir_all(parent)?;
         }
 
-        // Configure TensorRT provider
-        let tensorrt_provider = TensorRTExecutionProvider::default()
-            .with_device_id(0)  // Use the first GPU
-            .build();
-        
-        // Configure CUDA provider as fallback
-        let cuda_provider = CUDAExecutionProvider::default()
-            .with_device_id(0)  // Use the first GPU
-            .build();
-        
-        info!("Attempting to use TensorRT execution provider with CUDA fallback");
-        
-        // Load the model with optimizations and GPU support
-        let session = if model_path.exists() {
+        // Fail fast on invalid settings before a potentially long download
+        if !config.thread_affinity.is_empty() {
+            thread_affinity_entry(&config)?;
+        }
+        check_providers(&config.execution_providers)?;
+
+        if model_path.exists() {
             info!("Loading model from local file: {:?}", model_path);
-            Session::builder()?
-                .with_optimization_level(GraphOptimizationLevel::Level3)?
-                .with_execution_providers([tensorrt_provider, cuda_provider])?
-                .with_intra_threads(1)?
-                .commit_from_file(model_path)?
         } else {
-            info!("Model not found locally. Downloading from {}", MODEL_URL);
-            Session::builder()?
-                .with_optimization_level(GraphOptimizationLevel::Level3)?
-                .with_execution_providers([tensorrt_provider, cuda_provider])?
-                .with_intra_threads(1)?
-                .commit_from_url(MODEL_URL)?
-        };
-        
-        info!("Model loaded successfully with GPU support");
+            info!("Model not found locally. Downloading from {}", config.model_url);
+            download_model(&config.model_url, model_path, &config)?;
+        }
+
+        // Load the model with optimizations and GPU support
+        let (session, provider) = open_session(model_path, &config, &config.execution_providers)?;
 
         Ok(Self {
             session,
-            context: Array2::zeros((1, 64)),
+            config,
+            model_path: model_path.to_path_buf(),
+            provider,
+            context: Array2::zeros((1, CONTEXT_SIZE)),
             last_sr: 0,
             last_batch_size: 0,
+            timings: Vec::new(),
+            window_time: None,
         })
     }
 
+    /// Replace the loaded model with the model for another language
+    /// 
+    /// The new model is kept next to the current model file, under the file
+    /// name of its download URL (e.g. `de_v1.onnx`). It is loaded from there
+    /// if present, else downloaded and verified with [`download_model`];
+    /// [`ModelConfig::model_sha256`] only applies to the model of
+    /// [`ModelConfig::model_url`]. The new model is swapped in place and any
+    /// in-flight state is cleared on switch: the carried context is reset
+    /// exactly as if the model had just been constructed.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lang` - Language whose model should be loaded
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * No ONNX model is published for the language
+    /// * The model cannot be downloaded or loaded
+    pub fn reload(&mut self, lang: Language) -> Result<()> {
+        let url = lang.model_url().ok_or_else(|| {
+            Error::ModelLoad(format!("No ONNX model is available for {:?}", lang))
+        })?;
+
+        let file_name = url.rsplit('/').next().unwrap_or(url);
+        let path = self.model_path.with_file_name(file_name);
+        if path.exists() {
+            info!("Switching model to {:?} from local file: {:?}", lang, path);
+        } else {
+            info!("Switching model to {:?}. Downloading from {}", lang, url);
+            let mut config = self.config.clone();
+            if url != config.model_url {
+                config.model_sha256 = None;
+            }
+            download_model(url, &path, &config)?;
+        }
+
+        let providers = self.config.execution_providers.clone();
+        let (session, provider) = open_session(&path, &self.config, &providers)?;
+        self.session = session;
+        self.model_path = path;
+        self.provider = provider;
+        self.reset_states(1);
+        self.last_sr = 0;
+        self.window_time = None;
+
+        Ok(())
+    }
+
+    /// Get the execution provider running inference
+    /// 
+    /// This is the first registered provider that is available on this
+    /// machine, or [`Provider::Cpu`] if none of them is.
+    pub fn active_provider(&self) -> Provider {
+        self.provider
+    }
+
+    /// Summarize the inference call durations recorded so far
+    /// 
+    /// # Returns
+    /// 
+    /// The summary, or `None` if [`ModelConfig::record_timings`] is off or no
+    /// inference has run since the last [`SileroVAD::clear_timings`]
+    pub fn timing_stats(&self) -> Option<TimingStats> {
+        TimingStats::from_durations(&self.timings)
+    }
+
+    /// Forget the recorded inference call durations
+    pub fn clear_timings(&mut self) {
+        self.timings.clear();
+    }
+
+    /// Estimate how long detection on a buffer will take
+    /// 
+    /// The first call calibrates the per-window inference time on the active
+    /// provider with a few silent windows; the result is cached until the
+    /// session is rebuilt (see [`SileroVAD::reload`] and
+    /// [`SileroVAD::force_cpu`]). The carried context and recorded timings are
+    /// left untouched. The estimate covers inference only and is meant for
+    /// progress display, not as a guarantee.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio_len` - Number of samples to process
+    /// * `sr` - Sampling rate of the audio
+    /// 
+    /// # Returns
+    /// 
+    /// The extrapolated processing time for all windows of the buffer
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The sampling rate is not supported
+    /// * The calibration inference fails
+    pub fn estimate_processing_time(&mut self, audio_len: usize, sr: u32) -> Result<Duration> {
+        check_sampling_rate(sr)?;
+        let window_size = crate::vad::window_size_for(sr);
+        let window_time = match self.window_time {
+            Some(window_time) => window_time,
+            None => {
+                let window_time = self.calibrate(window_size, sr)?;
+                self.window_time = Some(window_time);
+                window_time
+            }
+        };
+
+        let windows = audio_len.div_ceil(window_size);
+        Ok(window_time * windows as u32)
+    }
+
+    /// Measure the inference time of one window, restoring the model state afterwards
+    fn calibrate(&mut self, window_size: usize, sr: u32) -> Result<Duration> {
+        // The first run absorbs one-off session warm-up costs
+        const RUNS: usize = 3;
+        let saved = (self.context.clone(), self.last_sr, self.last_batch_size, self.timings.len());
+        let silence = Array2::zeros((1, window_size));
+
+        let mut fastest = Duration::MAX;
+        let mut result = Ok(());
+        for _ in 0..RUNS {
+            let started = Instant::now();
+            if let Err(e) = self.run(&silence.view(), sr) {
+                result = Err(e);
+                break;
+            }
+            fastest = fastest.min(started.elapsed());
+        }
+
+        let (context, last_sr, last_batch_size, timings) = saved;
+        self.context = context;
+        self.last_sr = last_sr;
+        self.last_batch_size = last_batch_size;
+        self.timings.truncate(timings);
+        result.map(|_| fastest)
+    }
+
+    /// Rebuild the session to run on the CPU only
+    /// 
+    /// Execution providers cannot be swapped on a live session, so the model
+    /// is loaded again from the file it was loaded from (after
+    /// [`SileroVAD::reload`], the file of the new language), without any
+    /// network access. Useful to recover when GPU inference misbehaves. The
+    /// carried context is reset.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the model file cannot be loaded again, e.g. because
+    /// it was removed; the current session is kept then
+    pub fn force_cpu(&mut self) -> Result<()> {
+        let (session, provider) = open_session(&self.model_path, &self.config, &[Provider::Cpu])?;
+        self.session = session;
+        self.provider = provider;
+        self.reset_states(1);
+        self.last_sr = 0;
+        self.window_time = None;
+        Ok(())
+    }
+
+    /// Get the metadata embedded in the loaded model
+    /// 
+    /// Useful to record which model produced a set of results. Returns a
+    /// default (empty) value for any field the model does not provide, and an
+    /// entirely empty value if the metadata cannot be read.
+    pub fn model_metadata(&self) -> ModelMetadata {
+        let metadata = match self.session.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                debug!("Model metadata unavailable: {}", e);
+                return ModelMetadata::default();
+            }
+        };
+
+        let custom = metadata
+            .custom_keys()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|key| {
+                let value = metadata.custom(&key).ok().flatten()?;
+                Some((key, value))
+            })
+            .collect();
+
+        ModelMetadata {
+            name: metadata.name().unwrap_or_default(),
+            producer: metadata.producer().unwrap_or_default(),
+            description: metadata.description().unwrap_or_default(),
+            version: metadata.version().unwrap_or_default(),
+            custom,
+        }
+    }
+
+    /// Check that the loaded model has the input/output signature of a Silero VAD model
+    /// 
+    /// Run automatically whenever a model is loaded, so a wrong download or a
+    /// corrupted mirror is reported at load time instead of failing
+    /// cryptically (or producing nonsense) on the first inference.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns `Error::ModelLoad` if the model lacks a 2D float `input` for
+    /// the audio window or a float probability output
+    pub fn validate(&self) -> Result<()> {
+        check_signature(&self.session, "The loaded model")
+    }
+
+    /// Get the context carried over from the previous chunk
+    /// 
+    /// Has one row of [`CONTEXT_SIZE`] samples per batch entry.
+    pub fn context(&self) -> ArrayView2<'_, f32> {
+        self.context.view()
+    }
+
+    /// Replace the context carried over from the previous chunk
+    /// 
+    /// Typically used to restore a context previously obtained from
+    /// [`SileroVAD::context`]. The batch size is taken from the number of rows.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the context doesn't have [`CONTEXT_SIZE`] columns
+    pub fn set_context(&mut self, context: Array2<f32>) -> Result<()> {
+        if context.ncols() != CONTEXT_SIZE {
+            return Err(Error::InvalidInput(format!(
+                "Context must have {} columns, got shape {:?}",
+                CONTEXT_SIZE,
+                context.shape()
+            )));
+        }
+        self.last_batch_size = context.nrows();
+        self.context = context;
+        Ok(())
+    }
+
     /// Reset the model's internal state
     /// 
     /// This should be called when processing a new audio stream or when
@@ -110,7 +538,8 @@ impl SileroVAD {
     /// 
     /// * `batch_size` - The new batch size for processing
     pub fn reset_states(&mut self, batch_size: usize) {
-        self.context = Array2::zeros((batch_size, 64));
+        self.context = Array2::zeros((batch_size, CONTEXT_SIZE));
+        self.last_batch_size = batch_size;
     }
 
     /// Validate input audio chunk
@@ -124,11 +553,51 @@ impl SileroVAD {
     /// 
     /// `Ok(())` if the input is valid, `Err` otherwise
     fn validate_input(&self, x: &ArrayView1<f32>, sr: u32) -> Result<()> {
-        if sr != 16000 {
-            return Err(Error::InvalidInput("Sampling rate must be 16kHz".into()));
+        check_sampling_rate(sr)?;
+        let expected = crate::vad::window_size_for(sr);
+        if x.len() != expected {
+            return Err(Error::InvalidInput(format!(
+                "Input chunk has {} samples but the model expects {} samples at {} Hz",
+                x.len(),
+                expected,
+                sr
+            )));
         }
-        if x.len() != 512 {
-            return Err(Error::InvalidInput("Input chunk must be 512 samples".into()));
+        Ok(())
+    }
+
+    /// Validate a batch of audio chunks before inference
+    /// 
+    /// The error names the received shape and the expected number of
+    /// columns, so a transposed or wrongly windowed array is easy to spot.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Batch with shape `[batch_size, window_size]`
+    /// * `sr` - Sampling rate of the audio
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The sampling rate is not supported
+    /// * The batch is empty
+    /// * The number of columns does not match the window size for `sr`
+    pub fn validate_batch(&self, x: &Array2<f32>, sr: u32) -> Result<()> {
+        check_sampling_rate(sr)?;
+        let expected = crate::vad::window_size_for(sr);
+        let (rows, cols) = x.dim();
+        if rows == 0 {
+            return Err(Error::InvalidInput(format!(
+                "Batch of shape [{}, {}] is empty; expected [batch_size, {}] at {} Hz",
+                rows, cols, expected, sr
+            )));
+        }
+        if cols != expected {
+            let hint = if rows == expected { " (is the batch transposed?)" } else { "" };
+            return Err(Error::InvalidInput(format!(
+                "Batch of shape [{}, {}] has {} columns but the model expects [batch_size, {}] at {} Hz{}",
+                rows, cols, cols, expected, sr, hint
+            )));
         }
         Ok(())
     }
@@ -151,50 +620,36 @@ impl SileroVAD {
     /// * The sampling rate is not supported
     /// * Model inference fails
     pub fn process_chunk(&mut self, x: &ArrayView1<f32>, sr: u32) -> Result<Array1<f32>> {
-        self.validate_input(x, sr)?;
-
-        let batch_size = 1;
-        if self.last_batch_size != batch_size {
-            self.reset_states(batch_size);
-        }
-
-        if self.last_sr != 0 && self.last_sr != sr {
-            self.reset_states(batch_size);
-        }
-
-        // Prepare input tensor
-        let input = Array2::from_shape_fn((batch_size, x.len() + 64), |(i, j)| {
-            if j < 64 {
-                self.context[[i, j]]
-            } else {
-                x[j - 64]
-            }
-        });
-
-        // Create input tensor
-        let input_shape = input.shape().to_vec();
-        let input_data = input.into_raw_vec();
-
-        debug!("Processing input tensor of shape {:?}", input_shape);
-
-        // Create input tensor with just the 'input' name
-        let inputs = vec![
-            ("input", Tensor::from_array((input_shape, input_data.clone()))?.into_dyn()),
-        ];
+        Ok(Array1::from_vec(self.process_chunk_classes(x, sr)?))
+    }
 
-        let outputs = self.session.run(inputs)?;
-        
-        // Update context from the last 64 elements of input_data
-        let context_data = input_data[input_data.len()-64..].to_vec();
-        self.context = Array2::from_shape_vec((batch_size, 64), context_data)
-            .map_err(|e| Error::InvalidInput(e.to_string()))?;
-        
-        self.last_sr = sr;
-        self.last_batch_size = batch_size;
+    /// Process a single audio chunk and return every class probability
+    /// 
+    /// Models with a single output return a one-element vector holding the
+    /// speech probability. Multi-class models (e.g. speech/music/noise) return
+    /// one probability per class, in the model's output order.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Audio chunk to process (must be 512 samples for 16kHz)
+    /// * `sr` - Sampling rate of the audio (must be 16kHz)
+    /// 
+    /// # Returns
+    /// 
+    /// Class probabilities for the chunk
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The input chunk size is invalid
+    /// * The sampling rate is not supported
+    /// * Model inference fails
+    pub fn process_chunk_classes(&mut self, x: &ArrayView1<f32>, sr: u32) -> Result<Vec<f32>> {
+        self.validate_input(x, sr)?;
 
-        // Return speech probability
-        let output_tensor = outputs[0].try_extract_tensor::<f32>()?;
-        Ok(Array1::from_vec(output_tensor.1.iter().cloned().collect::<Vec<f32>>()))
+        let batch = x.to_owned().insert_axis(Axis(0));
+        let probs = self.run(&batch.view(), sr)?;
+        Ok(probs.row(0).to_vec())
     }
 
     /// Process a batch of audio chunks
@@ -215,13 +670,36 @@ impl SileroVAD {
     /// * The sampling rate is not supported
     /// * Model inference fails
     pub fn process_batch(&mut self, x: &Array2<f32>, sr: u32) -> Result<Array1<f32>> {
-        if sr != 16000 {
-            return Err(Error::InvalidInput("Sampling rate must be 16kHz".into()));
-        }
-        if x.ncols() != 512 {
-            return Err(Error::InvalidInput("Input chunks must be 512 samples".into()));
-        }
+        Ok(self.process_batch_classes(x, sr)?.column(0).to_owned())
+    }
 
+    /// Process a batch of audio chunks and return every class probability
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Batch of audio chunks to process (each chunk must be 512 samples for 16kHz)
+    /// * `sr` - Sampling rate of the audio (must be 16kHz)
+    /// 
+    /// # Returns
+    /// 
+    /// Array of shape `[batch_size, num_classes]` with the class probabilities of each chunk
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The batch shape is invalid, see [`SileroVAD::validate_batch`]
+    /// * The sampling rate is not supported
+    /// * Model inference fails
+    pub fn process_batch_classes(&mut self, x: &Array2<f32>, sr: u32) -> Result<Array2<f32>> {
+        self.validate_batch(x, sr)?;
+        self.run(&x.view(), sr)
+    }
+
+    /// Run the model on validated input and update the carried context
+    /// 
+    /// The number of classes is derived from the size of the output tensor,
+    /// so both scalar and multi-class outputs are supported.
+    fn run(&mut self, x: &ArrayView2<f32>, sr: u32) -> Result<Array2<f32>> {
         let batch_size = x.nrows();
         if self.last_batch_size != batch_size {
             self.reset_states(batch_size);
@@ -232,37 +710,388 @@ impl SileroVAD {
         }
 
         // Prepare input tensor
-        let input = Array2::from_shape_fn((batch_size, x.ncols() + 64), |(i, j)| {
-            if j < 64 {
+        let input = Array2::from_shape_fn((batch_size, x.ncols() + CONTEXT_SIZE), |(i, j)| {
+            if j < CONTEXT_SIZE {
                 self.context[[i, j]]
             } else {
-                x[[i, j - 64]]
+                x[[i, j - CONTEXT_SIZE]]
             }
         });
 
+        // Each row carries its own context: the tail of that row's window
+        let next_context = input.slice(s![.., input.ncols() - CONTEXT_SIZE..]).to_owned();
+
         // Create input tensor
         let input_shape = input.shape().to_vec();
         let input_data = input.into_raw_vec();
 
-        debug!("Processing batch input tensor of shape {:?}", input_shape);
+        debug!("Processing input tensor of shape {:?}", input_shape);
 
         // Create input tensor with just the 'input' name
         let inputs = vec![
-            ("input", Tensor::from_array((input_shape, input_data.clone()))?.into_dyn()),
+            ("input", Tensor::from_array((input_shape, input_data))?.into_dyn()),
         ];
 
+        let started = self.config.record_timings.then(Instant::now);
         let outputs = self.session.run(inputs)?;
+        if let Some(started) = started {
+            self.timings.push(started.elapsed());
+        }
         
-        // Update context from the last 64 elements of input_data
-        let context_data = input_data[input_data.len()-64*batch_size..].to_vec();
-        self.context = Array2::from_shape_vec((batch_size, 64), context_data)
-            .map_err(|e| Error::InvalidInput(e.to_string()))?;
-        
+        self.context = next_context;
+
         self.last_sr = sr;
         self.last_batch_size = batch_size;
 
-        // Return speech probabilities
-        let output_tensor = outputs[0].try_extract_tensor::<f32>()?;
-        Ok(Array1::from_vec(output_tensor.1.iter().cloned().collect::<Vec<f32>>()))
+        // Return class probabilities, one row per chunk
+        let transform = self.config.output_transform;
+        let data: Vec<f32> = extract_probabilities(&outputs[0])?
+            .into_iter()
+            .map(|x| transform.apply(x))
+            .collect();
+        if data.is_empty() || data.len() % batch_size != 0 {
+            return Err(Error::InvalidInput(format!(
+                "Model produced {} output values for a batch of {}",
+                data.len(),
+                batch_size
+            )));
+        }
+        let num_classes = data.len() / batch_size;
+        Array2::from_shape_vec((batch_size, num_classes), data)
+            .map_err(|e| Error::InvalidInput(e.to_string()))
+    }
+}
+
+/// Sampling rates the model accepts
+/// 
+/// Window sizes follow from the rate, see `vad::window_size_for`.
+pub const SUPPORTED_SAMPLING_RATES: &[u32] = &[16000];
+
+/// Samples of the previous window prepended to each model input
+pub const CONTEXT_SIZE: usize = 64;
+
+/// Check that the model accepts audio at the given sampling rate
+pub(crate) fn check_sampling_rate(sr: u32) -> Result<()> {
+    if SUPPORTED_SAMPLING_RATES.contains(&sr) {
+        return Ok(());
+    }
+    Err(Error::InvalidInput(format!(
+        "Unsupported sampling rate {} Hz; supported rates are {:?} Hz. Resample the audio first, e.g. with utils::resample",
+        sr, SUPPORTED_SAMPLING_RATES
+    )))
+}
+
+/// Open a session from a model file with the given execution providers
+fn open_session(
+    path: &Path,
+    config: &ModelConfig,
+    providers: &[Provider],
+) -> Result<(Session, Provider)> {
+    let (builder, provider) = session_builder(config, providers)?;
+    let session = builder.commit_from_file(path)?;
+    let name = path.display().to_string();
+
+    check_signature(&session, &name)?;
+    events::model_loaded(&name, &format!("{:?}", provider));
+    Ok((session, provider))
+}
+
+/// Check that a session has the signature of a Silero VAD model
+/// 
+/// The model must take the audio window (with its context) as a 2D f32
+/// `input` and produce the speech probability as its first, float, output.
+/// `name` identifies the model file in the error.
+fn check_signature(session: &Session, name: &str) -> Result<()> {
+    let mismatch = |reason: String| {
+        Error::ModelLoad(format!(
+            "{} does not appear to be a Silero VAD model: {}",
+            name, reason
+        ))
+    };
+
+    let input = session
+        .inputs
+        .iter()
+        .find(|input| input.name == "input")
+        .ok_or_else(|| {
+            let names: Vec<_> = session.inputs.iter().map(|input| input.name.as_str()).collect();
+            mismatch(format!("no audio input named \"input\" (inputs: {:?})", names))
+        })?;
+    let input_rank = input.input_type.tensor_shape().map(|shape| shape.len());
+    if input.input_type.tensor_type() != Some(TensorElementType::Float32) || input_rank != Some(2) {
+        return Err(mismatch(format!(
+            "audio input is {:?}, expected a 2D f32 tensor",
+            input.input_type
+        )));
+    }
+
+    let output = session
+        .outputs
+        .first()
+        .ok_or_else(|| mismatch("the model has no outputs".into()))?;
+    match output.output_type.tensor_type() {
+        Some(TensorElementType::Float32 | TensorElementType::Float16 | TensorElementType::Float64) => {
+            Ok(())
+        }
+        _ => Err(mismatch(format!(
+            "probability output is {:?}, expected a float tensor",
+            output.output_type
+        ))),
+    }
+}
+
+/// Create a session builder with the default optimizations and the given execution providers
+/// 
+/// Returns the builder along with the provider that will run inference: the
+/// first registered provider available on this machine, or the CPU.
+fn session_builder(config: &ModelConfig, providers: &[Provider]) -> Result<(SessionBuilder, Provider)> {
+    check_providers(providers)?;
+    let mut dispatch = Vec::new();
+    let mut active = None;
+
+    for &provider in providers {
+        // Device providers use the first device
+        let (ep, available) = match provider {
+            Provider::TensorRT => register(TensorRTExecutionProvider::default().with_device_id(0)),
+            Provider::Cuda => register(CUDAExecutionProvider::default().with_device_id(0)),
+            Provider::DirectML => register(DirectMLExecutionProvider::default().with_device_id(0)),
+            Provider::CoreML => register(CoreMLExecutionProvider::default()),
+            Provider::ROCm => register(ROCmExecutionProvider::default().with_device_id(0)),
+            Provider::Cpu => break,
+        };
+        if available && active.is_none() {
+            active = Some(provider);
+        }
+        dispatch.push(ep);
+    }
+
+    events::providers_registered(&format!("{:?}", providers));
+
+    let mut builder = Session::builder()?
+        .with_optimization_level(GraphOptimizationLevel::Level3)?
+        .with_execution_providers(dispatch)?
+        .with_intra_threads(config.intra_threads.max(1))?;
+
+    if !config.thread_affinity.is_empty() {
+        builder = builder.with_config_entry(
+            "session.intra_op_thread_affinities",
+            thread_affinity_entry(config)?,
+        )?;
+    }
+    Ok((builder, active.unwrap_or(Provider::Cpu)))
+}
+
+/// Prepare an execution provider for registration and check whether it can run here
+fn register<E>(ep: E) -> (ExecutionProviderDispatch, bool)
+where
+    E: ExecutionProvider + Into<ExecutionProviderDispatch>,
+{
+    let available = ep.is_available().unwrap_or(false);
+    (ep.into(), available)
+}
+
+/// Read model output values as f32, whatever float type the model was exported with
+/// 
+/// Quantized or half-precision exports may produce f16 (or f64) outputs, which
+/// are converted instead of failing on a type mismatch.
+/// 
+/// # Errors
+/// 
+/// Returns an error naming the actual output type if it is not a float tensor
+fn extract_probabilities(output: &DynValue) -> Result<Vec<f32>> {
+    if let Ok((_, data)) = output.try_extract_tensor::<f32>() {
+        return Ok(data.to_vec());
+    }
+    if let Ok((_, data)) = output.try_extract_tensor::<half::f16>() {
+        return Ok(data.iter().map(|x| x.to_f32()).collect());
+    }
+    if let Ok((_, data)) = output.try_extract_tensor::<f64>() {
+        return Ok(data.iter().map(|&x| x as f32).collect());
     }
-} 
\ No newline at end of file
+    Err(Error::ModelLoad(format!(
+        "Unsupported model output type {:?}; expected a f32, f16 or f64 tensor",
+        output.dtype()
+    )))
+}
+
+/// Check that the CPU, the implicit final fallback, is not listed before other providers
+fn check_providers(providers: &[Provider]) -> Result<()> {
+    match providers.iter().position(|&p| p == Provider::Cpu) {
+        Some(index) if index + 1 < providers.len() => Err(Error::InvalidInput(format!(
+            "Provider::Cpu must be the last execution provider, but {:?} follow it",
+            &providers[index + 1..]
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Format the thread affinity setting as ONNX Runtime expects it, e.g. `"1,2;3"`
+fn thread_affinity_entry(config: &ModelConfig) -> Result<String> {
+    let workers = config.intra_threads.max(1) - 1;
+    if config.thread_affinity.len() != workers {
+        return Err(Error::InvalidInput(format!(
+            "Thread affinity lists {} thread(s) but {} intra-op thread(s) use {} worker(s)",
+            config.thread_affinity.len(),
+            config.intra_threads,
+            workers
+        )));
+    }
+    if config.thread_affinity.iter().any(|cores| cores.is_empty() || cores.contains(&0)) {
+        return Err(Error::InvalidInput(
+            "Thread affinity entries must list at least one 1-based processor id".into(),
+        ));
+    }
+
+    Ok(config
+        .thread_affinity
+        .iter()
+        .map(|cores| cores.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(","))
+        .collect::<Vec<_>>()
+        .join(";"))
+}
+
+/// Download a model file, resuming an interrupted download
+/// 
+/// The model is streamed into a `.part` file next to `path`, which is renamed
+/// to `path` once it is verified: against [`ModelConfig::model_sha256`] if
+/// set, else against the size announced by the server or, when the server
+/// announces no size, by loading it as an ONNX model. When an
+/// attempt fails, the next one asks the server for the remaining bytes only
+/// (an HTTP range request), so a flaky connection does not restart a large
+/// download from scratch; the partial file also survives across runs.
+/// Servers without range support transparently restart the download.
+/// 
+/// # Arguments
+/// 
+/// * `url` - URL to download the model from
+/// * `path` - Destination of the model file
+/// * `config` - Retry settings, see [`ModelConfig`]
+/// 
+/// # Errors
+/// 
+/// Returns `Error::Download` if the model cannot be downloaded after all attempts
+pub fn download_model(url: &str, path: &Path, config: &ModelConfig) -> Result<()> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".part");
+    let partial = PathBuf::from(partial);
+
+    with_retries(url, config, || {
+        let size_checked = download_range(url, &partial)?;
+        verify_download(&partial, size_checked, config)
+    })?;
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+/// Check a completed download before it is put in place
+/// 
+/// A file failing the check is removed, so the next attempt starts over.
+fn verify_download(partial: &Path, size_checked: bool, config: &ModelConfig) -> Result<()> {
+    let check = match &config.model_sha256 {
+        Some(expected) => {
+            let digest = sha256_hex(partial)?;
+            if digest.eq_ignore_ascii_case(expected.trim()) {
+                Ok(())
+            } else {
+                Err(Error::Download(format!(
+                    "checksum mismatch: expected SHA-256 {}, got {}",
+                    expected, digest
+                )))
+            }
+        }
+        // A truncated body of unannounced size only shows when parsing it
+        None if !size_checked => Session::builder()
+            .and_then(|builder| builder.commit_from_file(partial))
+            .map(drop)
+            .map_err(|e| Error::Download(format!("downloaded model is unreadable: {}", e))),
+        None => Ok(()),
+    };
+    if check.is_err() {
+        fs::remove_file(partial)?;
+    }
+    check
+}
+
+/// SHA-256 digest of a file as a lowercase hex string
+fn sha256_hex(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Fetch the bytes of `url` missing from `partial`, appending them to it
+/// 
+/// Returns whether the completed file was checked against a size announced
+/// by the server.
+fn download_range(url: &str, partial: &Path) -> Result<bool> {
+    let offset = fs::metadata(partial).map(|m| m.len()).unwrap_or(0);
+    let mut request = ureq::get(url);
+    if offset > 0 {
+        request = request.set("Range", &format!("bytes={}-", offset));
+    }
+
+    let response = match request.call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(416, _)) => {
+            // The partial file no longer matches the remote file
+            fs::remove_file(partial)?;
+            return Err(Error::Download("resume range rejected, restarting download".into()));
+        }
+        Err(e) => return Err(Error::Download(e.to_string())),
+    };
+
+    let resumed = response.status() == 206;
+    let expected = if resumed {
+        response
+            .header("Content-Range")
+            .and_then(|range| range.rsplit('/').next())
+            .and_then(|total| total.parse::<u64>().ok())
+    } else {
+        response.header("Content-Length").and_then(|len| len.parse::<u64>().ok())
+    };
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(partial)?;
+    std::io::copy(&mut response.into_reader(), &mut file)?;
+    file.sync_all()?;
+
+    let size = fs::metadata(partial)?.len();
+    match expected {
+        Some(expected) if size != expected => Err(Error::Download(format!(
+            "incomplete download: received {} of {} bytes",
+            size, expected
+        ))),
+        _ => Ok(expected.is_some()),
+    }
+}
+
+/// Run a download step, retrying with exponential backoff
+fn with_retries<T>(url: &str, config: &ModelConfig, mut step: impl FnMut() -> Result<T>) -> Result<T> {
+    let attempts = config.download_attempts.max(1);
+    let mut delay = config.retry_base_delay;
+    let mut attempt = 1;
+
+    loop {
+        match step() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts => {
+                events::download_retry(url, attempt, attempts, &e.to_string());
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(Error::Download(format!(
+                    "failed to download {} after {} attempt(s): {}",
+                    url, attempts, e
+                )));
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Multi-channel voice activity detection
+//! 
+//! This module runs an independent detector on each channel of a recording,
+//! e.g. the two sides of a stereo call, and combines the per-channel results.
+
+use crate::{Error, Result, SpeechTimestamps, VADIterator};
+use ndarray::ArrayView2;
+
+/// Detector running one [`VADIterator`] per channel
+/// 
+/// Each channel needs its own iterator (and therefore its own model), since
+/// the model context is specific to the audio it has seen.
+pub struct MultiChannelVad {
+    detectors: Vec<VADIterator>,
+    segments: Vec<Vec<SpeechTimestamps>>,
+}
+
+impl MultiChannelVad {
+    /// Create a multi-channel detector from one iterator per channel
+    /// 
+    /// # Arguments
+    /// 
+    /// * `detectors` - Detectors, in channel order
+    pub fn new(detectors: Vec<VADIterator>) -> Self {
+        let segments = vec![Vec::new(); detectors.len()];
+        Self { detectors, segments }
+    }
+
+    /// Number of channels handled by the detector
+    pub fn channels(&self) -> usize {
+        self.detectors.len()
+    }
+
+    /// Detect speech on every channel of a recording
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Audio of shape `[channels, samples]`
+    /// * `min_speech_duration_ms` - Minimum duration of speech segments
+    /// * `max_speech_duration_s` - Maximum duration of speech segments
+    /// 
+    /// # Returns
+    /// 
+    /// Speech timestamps per channel
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The number of rows doesn't match the number of channels
+    /// * Model inference fails
+    pub fn detect(
+        &mut self,
+        audio: &ArrayView2<f32>,
+        min_speech_duration_ms: u32,
+        max_speech_duration_s: f32,
+    ) -> Result<&[Vec<SpeechTimestamps>]> {
+        if audio.nrows() != self.detectors.len() {
+            return Err(Error::InvalidInput(format!(
+                "Audio has {} channel(s), but the detector was created for {}",
+                audio.nrows(),
+                self.detectors.len()
+            )));
+        }
+
+        for (channel, detector) in self.detectors.iter_mut().enumerate() {
+            self.segments[channel] = detector.get_speech_timestamps(
+                &audio.row(channel),
+                min_speech_duration_ms,
+                max_speech_duration_s,
+                0,
+                0,
+            )?;
+        }
+
+        Ok(&self.segments)
+    }
+
+    /// Speech timestamps per channel from the last call to [`MultiChannelVad::detect`]
+    pub fn channel_segments(&self) -> &[Vec<SpeechTimestamps>] {
+        &self.segments
+    }
+
+    /// Regions where two or more channels are in speech at the same time
+    /// 
+    /// See [`crate::utils::overlap_segments`].
+    pub fn overlap_segments(&self) -> Vec<SpeechTimestamps> {
+        crate::utils::overlap_segments(&self.segments)
+    }
+}
@@ -4,11 +4,187 @@
 //! as well as processing audio chunks based on speech timestamps.
 
 use crate::{Error, Result};
-use ndarray::{Array1, s};
+use ndarray::{Array1, ArrayView1, ArrayView2, Axis, s};
 use std::path::Path;
 
+/// How multi-channel audio is turned into the returned sample buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelMode {
+    /// Average all channels into a single mono channel
+    Downmix,
+    /// Keep only the channel with the given zero-based index
+    Select(u16),
+    /// Keep all channels as interleaved frames
+    Keep,
+}
+
+/// Sample format used to decode WAV data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// 16-bit signed integer PCM
+    I16,
+    /// 24-bit signed integer PCM
+    I24,
+    /// 32-bit signed integer PCM
+    I32,
+    /// 32-bit IEEE float
+    F32,
+}
+
+impl SampleFormat {
+    /// Detect the sample format from a WAV header
+    fn from_spec(spec: &hound::WavSpec) -> Result<Self> {
+        match (spec.sample_format, spec.bits_per_sample) {
+            (hound::SampleFormat::Int, 1..=16) => Ok(SampleFormat::I16),
+            (hound::SampleFormat::Int, 17..=24) => Ok(SampleFormat::I24),
+            (hound::SampleFormat::Int, 25..=32) => Ok(SampleFormat::I32),
+            (hound::SampleFormat::Float, 32) => Ok(SampleFormat::F32),
+            (format, bits) => Err(Error::AudioProcessing(format!(
+                "Unsupported WAV sample format: {:?} with {} bits per sample",
+                format, bits
+            ))),
+        }
+    }
+}
+
+/// Options controlling how [`read_audio_opts`] decodes a WAV file
+/// 
+/// The defaults keep the file's own sampling rate, downmix to mono, leave the
+/// levels untouched and detect the sample format from the header.
+#[derive(Debug, Clone)]
+pub struct ReadOptions {
+    /// Sampling rate the caller expects; `None` accepts the file's rate
+    pub sampling_rate: Option<u32>,
+    /// Resample to `sampling_rate` instead of failing on a mismatch
+    pub resample: bool,
+    /// How multi-channel audio is handled
+    pub channels: ChannelMode,
+    /// Scale the output so that its peak magnitude is 1.0
+    pub normalize: bool,
+    /// Force a sample format instead of detecting it from the header
+    pub sample_format: Option<SampleFormat>,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self {
+            sampling_rate: None,
+            resample: false,
+            channels: ChannelMode::Downmix,
+            normalize: false,
+            sample_format: None,
+        }
+    }
+}
+
+/// Read audio from a WAV file with explicit decoding options
+/// 
+/// # Arguments
+/// 
+/// * `path` - Path to the WAV file
+/// * `options` - Decoding options, see [`ReadOptions`]
+/// 
+/// # Returns
+/// 
+/// Audio data as a 1D array of f32 samples in the range [-1.0, 1.0]
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// * The file cannot be opened
+/// * The file format is invalid or unsupported
+/// * The sampling rate doesn't match and resampling is disabled
+/// * The selected channel doesn't exist
+/// * The audio data cannot be read
+pub fn read_audio_opts<P: AsRef<Path>>(path: P, options: ReadOptions) -> Result<Array1<f32>> {
+    let mut reader = hound::WavReader::open(path).map_err(|e| Error::AudioProcessing(e.to_string()))?;
+    let spec = reader.spec();
+
+    if let Some(sampling_rate) = options.sampling_rate {
+        if spec.sample_rate != sampling_rate && !options.resample {
+            return Err(Error::AudioProcessing(format!(
+                "Audio file has sampling rate {}, but {} was requested",
+                spec.sample_rate,
+                sampling_rate
+            )));
+        }
+    }
+
+    let format = match options.sample_format {
+        Some(format) => format,
+        None => SampleFormat::from_spec(&spec)?,
+    };
+
+    let samples: Vec<f32> = match format {
+        SampleFormat::I16 => reader
+            .samples::<i16>()
+            .map(|s| s.map(|v| v as f32 / 32768.0))
+            .collect::<std::result::Result<Vec<f32>, _>>(),
+        SampleFormat::I24 => reader
+            .samples::<i32>()
+            .map(|s| s.map(|v| normalize_int(v, 24)))
+            .collect::<std::result::Result<Vec<f32>, _>>(),
+        SampleFormat::I32 => reader
+            .samples::<i32>()
+            .map(|s| s.map(|v| normalize_int(v, 32)))
+            .collect::<std::result::Result<Vec<f32>, _>>(),
+        SampleFormat::F32 => reader
+            .samples::<f32>()
+            .collect::<std::result::Result<Vec<f32>, _>>(),
+    }
+    .map_err(|e| Error::AudioProcessing(e.to_string()))?;
+
+    let channels = spec.channels.max(1) as usize;
+    let mut audio = match options.channels {
+        ChannelMode::Keep => Array1::from_vec(samples),
+        ChannelMode::Downmix => Array1::from_vec(
+            samples
+                .chunks_exact(channels)
+                .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+                .collect(),
+        ),
+        ChannelMode::Select(channel) => {
+            if channel as usize >= channels {
+                return Err(Error::InvalidInput(format!(
+                    "Channel {} requested, but the file has {} channel(s)",
+                    channel, channels
+                )));
+            }
+            Array1::from_vec(
+                samples
+                    .chunks_exact(channels)
+                    .map(|frame| frame[channel as usize])
+                    .collect(),
+            )
+        }
+    };
+
+    if let Some(sampling_rate) = options.sampling_rate {
+        if spec.sample_rate != sampling_rate {
+            if options.channels == ChannelMode::Keep && channels > 1 {
+                return Err(Error::InvalidInput(
+                    "Resampling is only supported for mono output".into(),
+                ));
+            }
+            audio = resample(&audio, spec.sample_rate, sampling_rate);
+        }
+    }
+
+    if options.normalize {
+        let peak = audio.iter().fold(0.0f32, |acc, &v| acc.max(v.abs()));
+        if peak > 0.0 {
+            audio.mapv_inplace(|v| v / peak);
+        }
+    }
+
+    Ok(audio)
+}
+
 /// Read audio from a WAV file
 /// 
+/// Multi-channel files are downmixed to mono and the sample format is detected
+/// from the header. Use [`read_audio_opts`] for finer control.
+/// 
 /// # Arguments
 /// 
 /// * `path` - Path to the WAV file
@@ -26,32 +202,490 @@ use std::path::Path;
 /// * The sampling rate doesn't match
 /// * The audio data cannot be read
 pub fn read_audio<P: AsRef<Path>>(path: P, sampling_rate: u32) -> Result<Array1<f32>> {
-    let mut reader = hound::WavReader::open(path).map_err(|e| Error::AudioProcessing(e.to_string()))?;
-    
-    if reader.spec().sample_rate != sampling_rate {
+    read_audio_opts(
+        path,
+        ReadOptions {
+            sampling_rate: Some(sampling_rate),
+            ..Default::default()
+        },
+    )
+}
+
+/// Read the sampling rate from a WAV file header without decoding the audio
+/// 
+/// # Arguments
+/// 
+/// * `path` - Path to the WAV file
+/// 
+/// # Returns
+/// 
+/// The sampling rate stored in the file
+/// 
+/// # Errors
+/// 
+/// Returns an error if the file cannot be opened or is not a valid WAV file
+pub fn wav_sampling_rate<P: AsRef<Path>>(path: P) -> Result<u32> {
+    let reader = hound::WavReader::open(path).map_err(|e| Error::AudioProcessing(e.to_string()))?;
+    Ok(reader.spec().sample_rate)
+}
+
+/// Sample encodings supported by [`MappedWav`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MappedFormat {
+    Pcm16,
+    Float32,
+}
+
+/// A WAV file memory-mapped for window-by-window reading, see [`read_audio_mmap`]
+/// 
+/// Samples stay in the mapping and are converted to mono f32 one window at a
+/// time, so multi-gigabyte files can be processed without loading them into
+/// memory and without the per-read overhead of a streaming reader.
+#[derive(Debug)]
+pub struct MappedWav {
+    mmap: memmap2::Mmap,
+    data: std::ops::Range<usize>,
+    format: MappedFormat,
+    big_endian: bool,
+    channels: u16,
+    sampling_rate: u32,
+}
+
+impl MappedWav {
+    /// Sampling rate of the audio
+    pub fn sampling_rate(&self) -> u32 {
+        self.sampling_rate
+    }
+
+    /// Number of interleaved channels in the file
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Number of frames (samples per channel)
+    pub fn len(&self) -> usize {
+        self.data.len() / self.frame_bytes()
+    }
+
+    /// Whether the file holds no audio
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read a span of frames as mono f32 samples
+    /// 
+    /// Channels are averaged. The span is clipped to the end of the audio.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `start` - First frame to read
+    /// * `frames` - Number of frames to read
+    pub fn read(&self, start: usize, frames: usize) -> Array1<f32> {
+        let end = start.saturating_add(frames).min(self.len());
+        let start = start.min(end);
+        let frame_bytes = self.frame_bytes();
+        let offset = self.data.start;
+        let bytes = &self.mmap[offset + start * frame_bytes..offset + end * frame_bytes];
+
+        let channels = self.channels as usize;
+        bytes
+            .chunks_exact(frame_bytes)
+            .map(|frame| {
+                let sum: f32 = frame
+                    .chunks_exact(frame_bytes / channels)
+                    .map(|sample| self.decode(sample))
+                    .sum();
+                sum / channels as f32
+            })
+            .collect()
+    }
+
+    /// Iterate over consecutive mono windows of the audio
+    /// 
+    /// A trailing partial window is dropped, matching the detector's windowing.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `window_size` - Frames per window
+    pub fn windows(&self, window_size: usize) -> impl Iterator<Item = Array1<f32>> + '_ {
+        let window_size = window_size.max(1);
+        (0..self.len() / window_size).map(move |i| self.read(i * window_size, window_size))
+    }
+
+    /// Read consecutive mono windows ahead on a background thread
+    /// 
+    /// Takes ownership of the mapping so reading (page faults on a slow disk)
+    /// overlaps with inference on the consumer side. Windows are as in
+    /// [`MappedWav::windows`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `window_size` - Frames per window
+    /// * `depth` - Number of windows read ahead of the consumer (at least 1)
+    pub fn prefetch_windows(self, window_size: usize, depth: usize) -> PrefetchWindows {
+        let window_size = window_size.max(1);
+        let windows = (0..self.len() / window_size)
+            .map(move |i| self.read(i * window_size, window_size));
+        PrefetchWindows::new(windows, depth)
+    }
+
+    fn frame_bytes(&self) -> usize {
+        let sample_bytes = match self.format {
+            MappedFormat::Pcm16 => 2,
+            MappedFormat::Float32 => 4,
+        };
+        sample_bytes * self.channels as usize
+    }
+
+    fn decode(&self, sample: &[u8]) -> f32 {
+        match (self.format, self.big_endian) {
+            (MappedFormat::Pcm16, false) => {
+                i16::from_le_bytes([sample[0], sample[1]]) as f32 / 32768.0
+            }
+            (MappedFormat::Pcm16, true) => {
+                i16::from_be_bytes([sample[0], sample[1]]) as f32 / 32768.0
+            }
+            (MappedFormat::Float32, false) => {
+                f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]])
+            }
+            (MappedFormat::Float32, true) => {
+                f32::from_be_bytes([sample[0], sample[1], sample[2], sample[3]])
+            }
+        }
+    }
+}
+
+/// Windows produced ahead of time on a background thread
+/// 
+/// The source runs on its own thread and hands windows over through a
+/// bounded channel holding up to `depth` windows, so IO for the next windows
+/// proceeds while the consumer runs inference on the current one, while
+/// memory stays bounded. Dropping the iterator stops the reader and waits
+/// for it to finish the window it is reading. A panic in the source is
+/// re-raised on the consumer side once the windows read before it are used
+/// up, so a failed read is never mistaken for the end of the audio.
+#[derive(Debug)]
+pub struct PrefetchWindows {
+    receiver: Option<std::sync::mpsc::Receiver<Array1<f32>>>,
+    reader: Option<std::thread::JoinHandle<()>>,
+}
+
+impl PrefetchWindows {
+    /// Start reading windows from a source on a background thread
+    /// 
+    /// # Arguments
+    /// 
+    /// * `source` - Windows in order, e.g. decoded from a file
+    /// * `depth` - Number of windows read ahead of the consumer (at least 1)
+    pub fn new<I>(source: I, depth: usize) -> Self
+    where
+        I: IntoIterator<Item = Array1<f32>> + Send + 'static,
+    {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(depth.max(1));
+        let reader = std::thread::spawn(move || {
+            for window in source {
+                if sender.send(window).is_err() {
+                    // The consumer is gone
+                    break;
+                }
+            }
+        });
+        Self { receiver: Some(receiver), reader: Some(reader) }
+    }
+
+    /// Wait for the reader thread, re-raising a panic from the source
+    fn join_reader(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            if let Err(panic) = reader.join() {
+                if !std::thread::panicking() {
+                    std::panic::resume_unwind(panic);
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for PrefetchWindows {
+    type Item = Array1<f32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let window = self.receiver.as_ref()?.recv().ok();
+        if window.is_none() {
+            // The sender is gone: the source is exhausted or panicked
+            self.receiver = None;
+            self.join_reader();
+        }
+        window
+    }
+}
+
+impl Drop for PrefetchWindows {
+    fn drop(&mut self) {
+        // Closing the channel makes the reader's next send fail
+        self.receiver = None;
+        self.join_reader();
+    }
+}
+
+/// Memory-map a WAV file for window-by-window processing
+/// 
+/// Parses the RIFF (little-endian) or RIFX (big-endian) header and locates
+/// the `data` chunk wherever it is, skipping any other chunks. 16-bit PCM
+/// and 32-bit float files are supported, including their
+/// `WAVE_FORMAT_EXTENSIBLE` variants.
+/// 
+/// # Arguments
+/// 
+/// * `path` - Path to the WAV file
+/// 
+/// # Returns
+/// 
+/// The mapped file, see [`MappedWav`]
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// * The file cannot be opened or mapped
+/// * The file is not a valid WAV file
+/// * The sample format is not 16-bit PCM or 32-bit float
+pub fn read_audio_mmap<P: AsRef<Path>>(path: P) -> Result<MappedWav> {
+    let file = std::fs::File::open(path)?;
+    // SAFETY: the mapping is read-only; as with any mmap, the caller must not
+    // truncate or modify the file while it is mapped
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+    let invalid = |message: &str| Error::AudioProcessing(format!("Invalid WAV file: {}", message));
+
+    let big_endian = match mmap.get(0..4) {
+        Some(b"RIFF") => false,
+        Some(b"RIFX") => true,
+        _ => return Err(invalid("missing RIFF header")),
+    };
+    if mmap.get(8..12) != Some(&b"WAVE"[..]) {
+        return Err(invalid("missing WAVE identifier"));
+    }
+    let read_u16 = |at: usize| {
+        mmap.get(at..at + 2).map(|b| {
+            let bytes = [b[0], b[1]];
+            if big_endian { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) }
+        })
+    };
+    let read_u32 = |at: usize| {
+        mmap.get(at..at + 4).map(|b| {
+            let bytes = [b[0], b[1], b[2], b[3]];
+            if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) }
+        })
+    };
+
+    let mut fmt = None;
+    let mut data = None;
+    let mut offset = 12;
+    while let (Some(id), Some(size)) = (mmap.get(offset..offset + 4), read_u32(offset + 4)) {
+        let body = offset + 8;
+        let size = size as usize;
+        match id {
+            b"fmt " => {
+                let tag = read_u16(body).ok_or_else(|| invalid("truncated fmt chunk"))?;
+                // WAVE_FORMAT_EXTENSIBLE keeps the real format tag in its sub-format GUID
+                let tag = if tag == 0xFFFE { read_u16(body + 24) } else { Some(tag) };
+                let fields = (tag, read_u16(body + 2), read_u32(body + 4), read_u16(body + 14));
+                match fields {
+                    (Some(tag), Some(channels), Some(rate), Some(bits)) => {
+                        fmt = Some((tag, channels, rate, bits))
+                    }
+                    _ => return Err(invalid("truncated fmt chunk")),
+                }
+            }
+            b"data" => {
+                // Tolerate a data size running past the end, as left by interrupted recorders
+                data = Some(body..body.saturating_add(size).min(mmap.len()));
+                break;
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even size
+        offset = body.saturating_add(size + size % 2);
+    }
+
+    let (tag, channels, sampling_rate, bits) = fmt.ok_or_else(|| invalid("missing fmt chunk"))?;
+    let data = data.ok_or_else(|| invalid("missing data chunk"))?;
+    let format = match (tag, bits) {
+        (1, 16) => MappedFormat::Pcm16,
+        (3, 32) => MappedFormat::Float32,
+        _ => {
+            return Err(Error::AudioProcessing(format!(
+                "Unsupported WAV sample format (format tag {}, {} bits); \
+                 only 16-bit PCM and 32-bit float can be memory-mapped",
+                tag, bits
+            )))
+        }
+    };
+    if channels == 0 {
+        return Err(invalid("zero channels"));
+    }
+
+    Ok(MappedWav { mmap, data, format, big_endian, channels, sampling_rate })
+}
+
+/// Read audio from a headerless raw PCM file of little-endian f32 samples
+/// 
+/// Since the file carries no header, the sampling rate and channel count must
+/// be supplied by the caller. Multi-channel data is expected to be interleaved
+/// and is downmixed to mono.
+/// 
+/// # Arguments
+/// 
+/// * `path` - Path to the raw PCM file
+/// * `sampling_rate` - Sampling rate the samples were recorded at
+/// * `channels` - Number of interleaved channels
+/// 
+/// # Returns
+/// 
+/// Mono audio data as a 1D array of f32 samples
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// * The file cannot be read
+/// * The sampling rate or channel count is zero
+/// * The file length is not a whole number of frames
+pub fn read_raw_f32<P: AsRef<Path>>(path: P, sampling_rate: u32, channels: u16) -> Result<Array1<f32>> {
+    if sampling_rate == 0 {
+        return Err(Error::InvalidInput("Sampling rate must be greater than zero".into()));
+    }
+    if channels == 0 {
+        return Err(Error::InvalidInput("Channel count must be greater than zero".into()));
+    }
+
+    let bytes = std::fs::read(path)?;
+    let frame_bytes = 4 * channels as usize;
+    if bytes.len() % frame_bytes != 0 {
         return Err(Error::AudioProcessing(format!(
-            "Audio file has sampling rate {}, but {} was requested",
-            reader.spec().sample_rate,
-            sampling_rate
+            "Raw file length {} bytes is not a whole number of {}-channel f32 frames",
+            bytes.len(),
+            channels
         )));
     }
 
-    let samples: Vec<f32> = reader
-        .samples::<i16>()
-        .map(|s| s.map_err(|e| Error::AudioProcessing(e.to_string())))
-        .map(|s| s.map(|v| v as f32 / 32768.0))
-        .collect::<Result<Vec<f32>>>()?;
+    let samples: Vec<f32> = bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
 
-    Ok(Array1::from_vec(samples))
+    let channels = channels as usize;
+    Ok(Array1::from_vec(
+        samples
+            .chunks_exact(channels)
+            .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+            .collect(),
+    ))
 }
 
-/// Save audio to a WAV file
+/// Convert integer samples stored in i32 containers to mono f32
+/// 
+/// Samples are normalized by their actual bit depth rather than the container
+/// size, so 24-bit audio stored in i32 (values in `[-2^23, 2^23)`) maps to
+/// `[-1.0, 1.0)` without first being truncated to 16 bits. Interleaved
+/// multi-channel data is downmixed by averaging. WAV files with 24- or 32-bit
+/// samples go through the same conversion in [`read_audio_opts`].
+/// 
+/// # Arguments
+/// 
+/// * `samples` - Interleaved integer samples
+/// * `channels` - Number of interleaved channels
+/// * `bits` - Bit depth of the samples (1 to 32)
+/// 
+/// # Returns
+/// 
+/// Mono audio data as a 1D array of f32 samples
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// * The channel count is zero
+/// * The bit depth is outside 1 to 32
+/// * The sample count is not a multiple of the channel count
+pub fn i32_to_mono_f32(samples: &[i32], channels: u16, bits: u16) -> Result<Array1<f32>> {
+    if channels == 0 {
+        return Err(Error::InvalidInput("Channel count must be greater than zero".into()));
+    }
+    if !(1..=32).contains(&bits) {
+        return Err(Error::InvalidInput(format!("Unsupported bit depth: {}", bits)));
+    }
+    let channels = channels as usize;
+    if samples.len() % channels != 0 {
+        return Err(Error::InvalidInput(format!(
+            "{} samples is not a whole number of {}-channel frames",
+            samples.len(),
+            channels
+        )));
+    }
+
+    Ok(Array1::from_vec(
+        samples
+            .chunks_exact(channels)
+            .map(|frame| {
+                frame.iter().map(|&v| normalize_int(v, bits)).sum::<f32>() / channels as f32
+            })
+            .collect(),
+    ))
+}
+
+/// Scale an integer sample of the given bit depth to [-1.0, 1.0)
+fn normalize_int(value: i32, bits: u16) -> f32 {
+    (value as f64 / (1u64 << (bits - 1)) as f64) as f32
+}
+
+/// Resample mono audio using linear interpolation
+/// 
+/// # Arguments
+/// 
+/// * `audio` - Audio data to resample
+/// * `from_rate` - Sampling rate of `audio`
+/// * `to_rate` - Desired sampling rate
+/// 
+/// # Returns
+/// 
+/// Resampled audio data
+pub fn resample(audio: &Array1<f32>, from_rate: u32, to_rate: u32) -> Array1<f32> {
+    if from_rate == to_rate || from_rate == 0 || to_rate == 0 || audio.is_empty() {
+        return audio.clone();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = ((audio.len() as f64) / ratio).round() as usize;
+    let last = audio.len() - 1;
+
+    Array1::from_shape_fn(out_len, |i| {
+        let pos = i as f64 * ratio;
+        let idx = (pos.floor() as usize).min(last);
+        let next = (idx + 1).min(last);
+        let frac = (pos - idx as f64) as f32;
+        audio[idx] * (1.0 - frac) + audio[next] * frac
+    })
+}
+
+/// Options controlling how [`save_audio_opts`] encodes a WAV file
+/// 
+/// The defaults truncate samples to 16-bit PCM without dither, so audio read
+/// with [`read_audio`] round-trips bit-exactly.
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    /// Add triangular-PDF dither of ±1 LSB before quantizing to 16 bits
+    /// 
+    /// Dither decorrelates the quantization error from the signal, trading
+    /// audible distortion in quiet passages for a low, constant noise floor.
+    /// The noise sequence is deterministic, so output is reproducible.
+    pub dither: bool,
+}
+
+/// Save audio to a WAV file with explicit encoding options
 /// 
 /// # Arguments
 /// 
 /// * `path` - Path to save the WAV file
 /// * `audio` - Audio data as a 1D array of f32 samples
 /// * `sampling_rate` - Sampling rate of the audio
+/// * `options` - Encoding options, see [`WriteOptions`]
 /// 
 /// # Errors
 /// 
@@ -59,7 +693,12 @@ pub fn read_audio<P: AsRef<Path>>(path: P, sampling_rate: u32) -> Result<Array1<
 /// * The file cannot be created
 /// * The audio data cannot be written
 /// * The WAV file cannot be finalized
-pub fn save_audio<P: AsRef<Path>>(path: P, audio: &Array1<f32>, sampling_rate: u32) -> Result<()> {
+pub fn save_audio_opts<P: AsRef<Path>>(
+    path: P,
+    audio: &Array1<f32>,
+    sampling_rate: u32,
+    options: WriteOptions,
+) -> Result<()> {
     let spec = hound::WavSpec {
         channels: 1,
         sample_rate: sampling_rate,
@@ -70,8 +709,16 @@ pub fn save_audio<P: AsRef<Path>>(path: P, audio: &Array1<f32>, sampling_rate: u
     let mut writer = hound::WavWriter::create(path, spec)
         .map_err(|e| Error::AudioProcessing(e.to_string()))?;
 
+    let mut rng = 0x9E37_79B9_7F4A_7C15u64;
     for &sample in audio.iter() {
-        let sample = (sample * 32768.0).max(-32768.0).min(32767.0) as i16;
+        let scaled = sample * 32768.0;
+        let scaled = if options.dither {
+            // The sum of two uniform values in [-0.5, 0.5) has a triangular PDF
+            (scaled + uniform_noise(&mut rng) + uniform_noise(&mut rng)).round()
+        } else {
+            scaled
+        };
+        let sample = scaled.clamp(-32768.0, 32767.0) as i16;
         writer
             .write_sample(sample)
             .map_err(|e| Error::AudioProcessing(e.to_string()))?;
@@ -84,14 +731,54 @@ pub fn save_audio<P: AsRef<Path>>(path: P, audio: &Array1<f32>, sampling_rate: u
     Ok(())
 }
 
+/// Save audio to a WAV file
+/// 
+/// Samples are truncated to 16-bit PCM. Use [`save_audio_opts`] to dither.
+/// 
+/// # Arguments
+/// 
+/// * `path` - Path to save the WAV file
+/// * `audio` - Audio data as a 1D array of f32 samples
+/// * `sampling_rate` - Sampling rate of the audio
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// * The file cannot be created
+/// * The audio data cannot be written
+/// * The WAV file cannot be finalized
+pub fn save_audio<P: AsRef<Path>>(path: P, audio: &Array1<f32>, sampling_rate: u32) -> Result<()> {
+    save_audio_opts(path, audio, sampling_rate, WriteOptions::default())
+}
+
+/// Draw a uniform value in [-0.5, 0.5) from a xorshift generator
+fn uniform_noise(state: &mut u64) -> f32 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    (*state >> 40) as f32 / (1u64 << 24) as f32 - 0.5
+}
+
+/// How [`collect_chunks_with`] handles segments that overlap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlapPolicy {
+    /// Extract each overlapped sample once, as if the segments were merged
+    #[default]
+    Merge,
+    /// Reject overlapping segments with an error
+    Error,
+}
+
 /// Collect audio chunks based on speech timestamps
 /// 
 /// This function extracts audio segments corresponding to speech timestamps
-/// and concatenates them into a single audio array.
+/// and concatenates them into a single audio array. Overlapping segments
+/// (e.g. after padding) are merged so no sample is duplicated; see
+/// [`collect_chunks_with`] to reject them instead.
 /// 
 /// # Arguments
 /// 
-/// * `timestamps` - Speech timestamps to extract
+/// * `timestamps` - Speech timestamps to extract, in chronological order
 /// * `audio` - Complete audio data
 /// * `sampling_rate` - Sampling rate of the audio
 /// 
@@ -109,60 +796,39 @@ pub fn collect_chunks(
     audio: &Array1<f32>,
     sampling_rate: u32,
 ) -> Result<Array1<f32>> {
-    let mut result = Vec::new();
-    let samples_per_second = sampling_rate as usize;
-
-    for ts in timestamps {
-        let start_sample = (ts.start * samples_per_second as f32) as usize;
-        let end_sample = (ts.end * samples_per_second as f32) as usize;
-        
-        if start_sample >= audio.len() || end_sample > audio.len() {
-            return Err(Error::InvalidInput(format!(
-                "Timestamp out of bounds: {} - {} (audio length: {})",
-                start_sample,
-                end_sample,
-                audio.len()
-            )));
-        }
-
-        result.extend_from_slice(&audio.slice(s![start_sample..end_sample]).to_vec());
-    }
-
-    Ok(Array1::from_vec(result))
+    collect_chunks_with(timestamps, audio, sampling_rate, OverlapPolicy::Merge)
 }
 
-/// Drop audio chunks based on speech timestamps
-/// 
-/// This function removes audio segments corresponding to speech timestamps
-/// and concatenates the remaining segments.
+/// Collect audio chunks based on speech timestamps with explicit overlap handling
 /// 
 /// # Arguments
 /// 
-/// * `timestamps` - Speech timestamps to remove
+/// * `timestamps` - Speech timestamps to extract, in chronological order
 /// * `audio` - Complete audio data
 /// * `sampling_rate` - Sampling rate of the audio
+/// * `overlap` - What to do when a segment starts before the previous one ends
 /// 
 /// # Returns
 /// 
-/// Audio with speech segments removed as a 1D array
+/// Concatenated audio segments as a 1D array
 /// 
 /// # Errors
 /// 
 /// Returns an error if:
 /// * Any timestamp is out of bounds
-/// * The audio data is invalid
-pub fn drop_chunks(
+/// * Segments overlap and `overlap` is [`OverlapPolicy::Error`]
+pub fn collect_chunks_with(
     timestamps: &[crate::vad::SpeechTimestamps],
     audio: &Array1<f32>,
     sampling_rate: u32,
+    overlap: OverlapPolicy,
 ) -> Result<Array1<f32>> {
     let mut result = Vec::new();
-    let samples_per_second = sampling_rate as usize;
-    let mut current_pos = 0;
+    // End of the audio extracted so far
+    let mut cursor = 0;
 
     for ts in timestamps {
-        let start_sample = (ts.start * samples_per_second as f32) as usize;
-        let end_sample = (ts.end * samples_per_second as f32) as usize;
+        let std::ops::Range { start: start_sample, end: end_sample } = sample_range(ts, sampling_rate);
         
         if start_sample >= audio.len() || end_sample > audio.len() {
             return Err(Error::InvalidInput(format!(
@@ -173,15 +839,1395 @@ pub fn drop_chunks(
             )));
         }
 
-        if start_sample > current_pos {
-            result.extend_from_slice(&audio.slice(s![current_pos..start_sample]).to_vec());
+        if start_sample < cursor && overlap == OverlapPolicy::Error {
+            return Err(Error::InvalidInput(format!(
+                "Segment starting at sample {} overlaps the previous segment ending at sample {}",
+                start_sample, cursor
+            )));
         }
-        current_pos = end_sample;
-    }
 
-    if current_pos < audio.len() {
-        result.extend_from_slice(&audio.slice(s![current_pos..]).to_vec());
+        let start_sample = start_sample.max(cursor);
+        if end_sample > start_sample {
+            result.extend_from_slice(&audio.slice(s![start_sample..end_sample]).to_vec());
+        }
+        cursor = cursor.max(end_sample);
     }
 
     Ok(Array1::from_vec(result))
-} 
\ No newline at end of file
+}
+
+/// Extract each speech segment together with the audio around it
+/// 
+/// Meant for manual review: every segment is widened by `context_s` on both
+/// sides (clamped to the audio), unlike `speech_pad_ms`, which moves the
+/// detected boundaries themselves. Segments whose widened spans overlap or
+/// touch are returned as a single clip, so no audio is repeated.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps in chronological order
+/// * `audio` - Complete audio data
+/// * `sampling_rate` - Sampling rate of the audio
+/// * `context_s` - Seconds of surrounding audio to include before and after each segment
+/// 
+/// # Returns
+/// 
+/// One clip per (merged) segment, in order
+pub fn collect_chunks_with_context(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    audio: &Array1<f32>,
+    sampling_rate: u32,
+    context_s: f32,
+) -> Vec<Array1<f32>> {
+    let context = (context_s.max(0.0) * sampling_rate as f32) as usize;
+    let mut spans: Vec<std::ops::Range<usize>> = Vec::new();
+
+    for ts in timestamps {
+        let range = sample_range(ts, sampling_rate);
+        let end = (range.end + context).min(audio.len());
+        let start = range.start.saturating_sub(context).min(end);
+        match spans.last_mut() {
+            Some(last) if start <= last.end => last.end = last.end.max(end),
+            _ => spans.push(start..end),
+        }
+    }
+
+    spans
+        .into_iter()
+        .map(|span| audio.slice(s![span]).to_owned())
+        .collect()
+}
+
+/// Concatenate speech segments with a stretch of silence between them
+/// 
+/// A review-friendly variant of [`collect_chunks`]: the inserted silence makes
+/// segment boundaries audible when listening to the result. Segments are
+/// extracted as-is, so overlapping segments repeat their shared audio, and
+/// segments reaching past the end of the audio are clipped to it.
+/// 
+/// # Arguments
+/// 
+/// * `audio` - Complete audio data
+/// * `timestamps` - Speech timestamps to extract, in order
+/// * `sampling_rate` - Sampling rate of the audio
+/// * `gap_ms` - Silence inserted between consecutive segments
+/// 
+/// # Returns
+/// 
+/// The segments joined by `gap_ms` of silence, without leading or trailing silence
+pub fn concat_segments_with_gaps(
+    audio: &Array1<f32>,
+    timestamps: &[crate::vad::SpeechTimestamps],
+    sampling_rate: u32,
+    gap_ms: u32,
+) -> Array1<f32> {
+    let gap = vec![0.0; ms_to_samples(gap_ms, sampling_rate)];
+    let mut result = Vec::new();
+
+    for (i, ts) in timestamps.iter().enumerate() {
+        if i > 0 {
+            result.extend_from_slice(&gap);
+        }
+        let range = sample_range(ts, sampling_rate);
+        let end = range.end.min(audio.len());
+        let start = range.start.min(end);
+        result.extend(audio.slice(s![start..end]).iter());
+    }
+
+    Array1::from_vec(result)
+}
+
+/// Drop audio chunks based on speech timestamps
+/// 
+/// This function removes audio segments corresponding to speech timestamps
+/// and concatenates the remaining segments.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps to remove
+/// * `audio` - Complete audio data
+/// * `sampling_rate` - Sampling rate of the audio
+/// 
+/// # Returns
+/// 
+/// Audio with speech segments removed as a 1D array
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// * Any timestamp is out of bounds
+/// * The audio data is invalid
+pub fn drop_chunks(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    audio: &Array1<f32>,
+    sampling_rate: u32,
+) -> Result<Array1<f32>> {
+    let mut result = Vec::new();
+    let mut current_pos = 0;
+
+    for ts in timestamps {
+        let std::ops::Range { start: start_sample, end: end_sample } = sample_range(ts, sampling_rate);
+        
+        if start_sample >= audio.len() || end_sample > audio.len() {
+            return Err(Error::InvalidInput(format!(
+                "Timestamp out of bounds: {} - {} (audio length: {})",
+                start_sample,
+                end_sample,
+                audio.len()
+            )));
+        }
+
+        if start_sample > current_pos {
+            result.extend_from_slice(&audio.slice(s![current_pos..start_sample]).to_vec());
+        }
+        current_pos = end_sample;
+    }
+
+    if current_pos < audio.len() {
+        result.extend_from_slice(&audio.slice(s![current_pos..]).to_vec());
+    }
+
+    Ok(Array1::from_vec(result))
+} 
+
+/// Silence everything outside speech segments, keeping the audio's timeline
+/// 
+/// Unlike [`drop_chunks`], which removes non-speech, this acts as a noise gate:
+/// the result has the same length as the input, so it stays in sync with
+/// other tracks. Segments reaching past the end of the audio are clipped.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps to keep
+/// * `audio` - Complete audio data
+/// * `sampling_rate` - Sampling rate of the audio
+/// 
+/// # Returns
+/// 
+/// A copy of the audio with non-speech samples set to zero
+pub fn gate_silence(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    audio: &Array1<f32>,
+    sampling_rate: u32,
+) -> Array1<f32> {
+    gate_silence_with_fade(timestamps, audio, sampling_rate, 0)
+}
+
+/// Silence everything outside speech segments, fading at the gate boundaries
+/// 
+/// Like [`gate_silence`], but each region of kept speech fades in and out
+/// over `fade_ms` (at most half the region) to avoid clicks where the gate
+/// opens and closes. Overlapping or touching segments form one region.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps to keep
+/// * `audio` - Complete audio data
+/// * `sampling_rate` - Sampling rate of the audio
+/// * `fade_ms` - Fade duration at each gate boundary (0 for a hard gate)
+/// 
+/// # Returns
+/// 
+/// A copy of the audio with non-speech samples set to zero
+pub fn gate_silence_with_fade(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    audio: &Array1<f32>,
+    sampling_rate: u32,
+    fade_ms: u32,
+) -> Array1<f32> {
+    let mut open = vec![false; audio.len()];
+    for ts in timestamps {
+        let range = sample_range(ts, sampling_rate);
+        let end = range.end.min(audio.len());
+        let start = range.start.min(end);
+        open[start..end].fill(true);
+    }
+
+    let mut gated = Array1::zeros(audio.len());
+    let fade = ms_to_samples(fade_ms, sampling_rate);
+    let mut i = 0;
+    while i < open.len() {
+        if !open[i] {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < open.len() && open[i] {
+            i += 1;
+        }
+
+        gated.slice_mut(s![start..i]).assign(&audio.slice(s![start..i]));
+        let ramp = fade.min((i - start) / 2);
+        for k in 0..ramp {
+            let gain = k as f32 / ramp as f32;
+            gated[start + k] *= gain;
+            gated[i - 1 - k] *= gain;
+        }
+    }
+
+    gated
+}
+
+/// Regroup speech segments into near-uniform windows
+/// 
+/// Consecutive segments are merged until a window reaches `target_s`, and no
+/// window is allowed to grow beyond `max_s`. Window boundaries always fall on
+/// the silence between segments, except when a single segment is itself longer
+/// than `max_s`: such a segment is split into equal pieces no longer than `max_s`,
+/// and windows ending at such an artificial cut are flagged with
+/// [`SplitReason::MaxDuration`](crate::vad::SplitReason::MaxDuration).
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps in chronological order
+/// * `target_s` - Desired window length in seconds
+/// * `max_s` - Maximum window length in seconds (raised to `target_s` if smaller)
+/// 
+/// # Returns
+/// 
+/// Regrouped windows covering all input speech
+pub fn rechunk_segments(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    target_s: f32,
+    max_s: f32,
+) -> Vec<crate::vad::SpeechTimestamps> {
+    let max_s = max_s.max(target_s);
+    if max_s.is_nan() || max_s <= 0.0 {
+        return timestamps.to_vec();
+    }
+
+    // Split segments that cannot fit into a single window
+    let mut pieces = Vec::with_capacity(timestamps.len());
+    for ts in timestamps {
+        let duration = ts.end - ts.start;
+        if duration <= max_s {
+            pieces.push(ts.clone());
+            continue;
+        }
+
+        let count = (duration / max_s).ceil() as usize;
+        let step = duration / count as f32;
+        for k in 0..count {
+            let mut piece = ts.clone();
+            piece.start = ts.start + k as f32 * step;
+            if k + 1 < count {
+                piece.end = ts.start + (k + 1) as f32 * step;
+                piece.split_reason = crate::vad::SplitReason::MaxDuration;
+            }
+            pieces.push(piece);
+        }
+    }
+
+    // Greedily merge pieces towards the target length
+    let mut result: Vec<crate::vad::SpeechTimestamps> = Vec::new();
+    for piece in pieces {
+        if let Some(current) = result.last_mut() {
+            let current_len = current.end - current.start;
+            if current_len < target_s && piece.end - current.start <= max_s {
+                // Duration-weighted confidence of the merged speech
+                let piece_len = piece.end - piece.start;
+                if current_len + piece_len > 0.0 {
+                    current.confidence = (current.confidence * current_len
+                        + piece.confidence * piece_len)
+                        / (current_len + piece_len);
+                }
+                current.peak_confidence = current.peak_confidence.max(piece.peak_confidence);
+                current.end = piece.end;
+                current.split_reason = piece.split_reason;
+                continue;
+            }
+        }
+        result.push(piece);
+    }
+
+    result
+}
+
+/// Compute the silence intervals complementary to speech segments
+/// 
+/// Returns the gaps between consecutive segments, including the leading
+/// silence before the first segment and the trailing silence after the last.
+/// Segments are clamped to `[0, total_duration_s]` and zero-length gaps
+/// (segments touching each other or the file boundaries) are omitted.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps in chronological order
+/// * `total_duration_s` - Total duration of the audio in seconds
+/// 
+/// # Returns
+/// 
+/// Silence intervals in chronological order
+pub fn invert_segments(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    total_duration_s: f32,
+) -> Vec<crate::vad::SpeechTimestamps> {
+    let mut result = Vec::new();
+    let mut cursor = 0.0f32;
+
+    for ts in timestamps {
+        let start = ts.start.clamp(0.0, total_duration_s);
+        let end = ts.end.clamp(0.0, total_duration_s);
+        if start > cursor {
+            result.push(crate::vad::SpeechTimestamps::new(cursor, start));
+        }
+        cursor = cursor.max(end);
+    }
+
+    if total_duration_s > cursor {
+        result.push(crate::vad::SpeechTimestamps::new(cursor, total_duration_s));
+    }
+
+    result
+}
+
+/// Compute the RMS energy envelope of audio on the VAD window grid
+/// 
+/// Windows are laid out exactly like the detector's: consecutive,
+/// non-overlapping windows of `window_size` samples starting at sample 0,
+/// with a trailing partial window ignored. Each entry can therefore be
+/// plotted directly against the probability of the matching VAD window.
+/// 
+/// # Arguments
+/// 
+/// * `audio` - Audio data
+/// * `sampling_rate` - Sampling rate of the audio
+/// * `window_size` - Window size in samples (512 for 16kHz)
+/// 
+/// # Returns
+/// 
+/// `(time_s, rms)` per window, where `time_s` is the window start time
+pub fn rms_envelope(audio: &Array1<f32>, sampling_rate: u32, window_size: usize) -> Vec<(f32, f32)> {
+    if window_size == 0 || sampling_rate == 0 {
+        return Vec::new();
+    }
+
+    audio
+        .exact_chunks(window_size)
+        .into_iter()
+        .enumerate()
+        .map(|(i, window)| {
+            let time = (i * window_size) as f32 / sampling_rate as f32;
+            let rms = (window.iter().map(|v| v * v).sum::<f32>() / window_size as f32).sqrt();
+            (time, rms)
+        })
+        .collect()
+}
+
+/// Keep only segments whose confidence reaches a minimum
+/// 
+/// This is a segment-level gate applied after detection, independent of the
+/// per-window detection threshold: a sensitive threshold can be used to find
+/// candidate segments, and weak ones are then discarded here.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps to filter
+/// * `min_confidence` - Minimum mean speech probability of a kept segment
+/// 
+/// # Returns
+/// 
+/// Segments with `confidence >= min_confidence`, in their original order
+pub fn filter_by_confidence(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    min_confidence: f32,
+) -> Vec<crate::vad::SpeechTimestamps> {
+    timestamps
+        .iter()
+        .filter(|ts| ts.confidence >= min_confidence)
+        .cloned()
+        .collect()
+}
+
+/// Bucket segments into confidence tiers
+/// 
+/// The tier boundaries split the confidence range into `tiers.len() + 1`
+/// buckets, from least to most confident, so a reviewer can work through
+/// the uncertain segments first. A segment whose confidence equals a
+/// boundary goes into the tier above it. Boundaries may be given in any
+/// order; a NaN confidence lands in the lowest tier.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps to group
+/// * `tiers` - Confidence boundaries between tiers, e.g. `[0.5, 0.8]`
+/// 
+/// # Returns
+/// 
+/// One bucket per tier, lowest first, each keeping the original segment order
+pub fn group_by_confidence(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    tiers: &[f32],
+) -> Vec<Vec<crate::vad::SpeechTimestamps>> {
+    let mut boundaries = tiers.to_vec();
+    boundaries.sort_by(|a, b| a.total_cmp(b));
+
+    let mut groups = vec![Vec::new(); boundaries.len() + 1];
+    for ts in timestamps {
+        let tier = boundaries.iter().filter(|&&b| ts.confidence >= b).count();
+        groups[tier].push(ts.clone());
+    }
+    groups
+}
+
+/// Count segments per duration bin, e.g. to profile utterance lengths in a corpus
+/// 
+/// Bin `i` covers durations in `[i * bin_width_s, (i + 1) * bin_width_s)`.
+/// There are `ceil(max_s / bin_width_s)` bins; segments of `max_s` or longer
+/// are counted in the last one.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech segments, e.g. gathered from many files
+/// * `bin_width_s` - Width of each bin in seconds
+/// * `max_s` - Duration from which segments all fall in the last bin
+/// 
+/// # Returns
+/// 
+/// Segment count per bin, or an empty vector if `bin_width_s` or `max_s` is
+/// not positive
+pub fn duration_histogram(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    bin_width_s: f32,
+    max_s: f32,
+) -> Vec<u32> {
+    let bins = if bin_width_s > 0.0 && max_s > 0.0 {
+        (max_s / bin_width_s).ceil() as usize
+    } else {
+        0
+    };
+    if bins == 0 {
+        return Vec::new();
+    }
+
+    let mut counts = vec![0; bins];
+    for ts in timestamps {
+        let duration = (ts.end - ts.start).max(0.0);
+        let bin = ((duration / bin_width_s) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    counts
+}
+
+/// Differences between the segments of two detection runs, see [`diff_segments`]
+#[derive(Debug, Clone, Default)]
+pub struct SegmentDiff {
+    /// Segments of the second run that match nothing in the first
+    pub added: Vec<crate::vad::SpeechTimestamps>,
+    /// Segments of the first run that match nothing in the second
+    pub removed: Vec<crate::vad::SpeechTimestamps>,
+    /// Matched segments whose boundaries moved, as `(first run, second run)` pairs
+    pub shifted: Vec<(crate::vad::SpeechTimestamps, crate::vad::SpeechTimestamps)>,
+    /// Number of matched segments with identical boundaries
+    pub unchanged: usize,
+}
+
+impl SegmentDiff {
+    /// Whether both runs produced the same segments
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.shifted.is_empty()
+    }
+}
+
+/// Compare the segments of two detection runs, e.g. before and after a parameter change
+/// 
+/// Segments are matched by overlap: each segment of `a`, in order, is paired
+/// with the not yet matched segment of `b` it overlaps the most. Matched
+/// segments whose start or end differ by more than a microsecond are
+/// reported as shifted. Segments left unmatched are added (in `b` only) or
+/// removed (in `a` only). A segment that was split or merged therefore shows
+/// up as one shifted segment plus added or removed ones.
+/// 
+/// # Arguments
+/// 
+/// * `a` - Segments of the first run
+/// * `b` - Segments of the second run
+/// 
+/// # Returns
+/// 
+/// The differences going from `a` to `b`
+pub fn diff_segments(
+    a: &[crate::vad::SpeechTimestamps],
+    b: &[crate::vad::SpeechTimestamps],
+) -> SegmentDiff {
+    const TOLERANCE_S: f32 = 1e-6;
+    let mut diff = SegmentDiff::default();
+    let mut matched = vec![false; b.len()];
+
+    for old in a {
+        let best = b
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !matched[*i])
+            .map(|(i, new)| (i, old.end.min(new.end) - old.start.max(new.start)))
+            .filter(|&(_, overlap)| overlap > 0.0)
+            .max_by(|x, y| x.1.total_cmp(&y.1));
+
+        match best {
+            Some((i, _)) => {
+                matched[i] = true;
+                let new = &b[i];
+                let moved = (new.start - old.start).abs() > TOLERANCE_S
+                    || (new.end - old.end).abs() > TOLERANCE_S;
+                if moved {
+                    diff.shifted.push((old.clone(), new.clone()));
+                } else {
+                    diff.unchanged += 1;
+                }
+            }
+            None => diff.removed.push(old.clone()),
+        }
+    }
+
+    diff.added = b
+        .iter()
+        .zip(&matched)
+        .filter(|(_, &m)| !m)
+        .map(|(ts, _)| ts.clone())
+        .collect();
+    diff
+}
+
+/// Export speech timestamps as a Praat TextGrid
+/// 
+/// Produces a TextGrid in Praat's long text format with a single interval
+/// tier named "speech" that covers `0..total_duration_s` without gaps:
+/// speech intervals are labelled "speech" and the silence between them is
+/// left unlabelled. Overlapping or touching segments are merged and segments
+/// are clipped to the total duration, as Praat requires contiguous,
+/// non-overlapping intervals.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps in chronological order
+/// * `total_duration_s` - Duration of the annotated audio in seconds
+/// 
+/// # Returns
+/// 
+/// The TextGrid file contents
+pub fn timestamps_to_textgrid(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    total_duration_s: f32,
+) -> String {
+    let total = total_duration_s.max(0.0);
+
+    // (xmin, xmax, label) intervals tiling the whole duration
+    let mut intervals: Vec<(f32, f32, &str)> = Vec::new();
+    let mut cursor = 0.0;
+    for ts in timestamps {
+        let start = ts.start.clamp(0.0, total);
+        let end = ts.end.clamp(0.0, total);
+        if end <= start {
+            continue;
+        }
+        match intervals.last_mut() {
+            Some(last) if last.2 == "speech" && start <= last.1 => {
+                last.1 = last.1.max(end);
+            }
+            _ => {
+                if start > cursor {
+                    intervals.push((cursor, start, ""));
+                }
+                intervals.push((start.max(cursor), end, "speech"));
+            }
+        }
+        cursor = cursor.max(end);
+    }
+    if cursor < total || intervals.is_empty() {
+        intervals.push((cursor, total, ""));
+    }
+
+    let round = |time: f32| Precision::default().round(time);
+    let total = round(total);
+    let mut grid = String::new();
+    grid.push_str("File type = \"ooTextFile\"\nObject class = \"TextGrid\"\n\n");
+    grid.push_str(&format!("xmin = 0 \nxmax = {} \ntiers? <exists> \n", total));
+    grid.push_str("size = 1 \nitem []: \n");
+    grid.push_str("    item [1]:\n");
+    grid.push_str("        class = \"IntervalTier\" \n        name = \"speech\" \n");
+    grid.push_str(&format!("        xmin = 0 \n        xmax = {} \n", total));
+    grid.push_str(&format!("        intervals: size = {} \n", intervals.len()));
+    for (i, (xmin, xmax, text)) in intervals.iter().enumerate() {
+        grid.push_str(&format!("        intervals [{}]:\n", i + 1));
+        grid.push_str(&format!("            xmin = {} \n", round(*xmin)));
+        grid.push_str(&format!("            xmax = {} \n", round(*xmax)));
+        grid.push_str(&format!("            text = \"{}\" \n", text));
+    }
+    grid
+}
+
+/// Save per-window speech probabilities as a WAV track
+/// 
+/// Each probability is held for `chunk_size` samples, producing a step signal
+/// in the range [0.0, 1.0] at the original sampling rate. Loaded next to the
+/// source audio in an audio editor, it lines up with the waveform and shows
+/// where the detector considered the signal to be speech.
+/// 
+/// # Arguments
+/// 
+/// * `path` - Path to save the WAV file
+/// * `probs` - Speech probability per window
+/// * `sampling_rate` - Sampling rate of the analyzed audio
+/// * `chunk_size` - Window size in samples used to compute the probabilities
+/// 
+/// # Errors
+/// 
+/// Returns an error if the WAV file cannot be written
+pub fn save_probability_track<P: AsRef<Path>>(
+    path: P,
+    probs: &[f32],
+    sampling_rate: u32,
+    chunk_size: usize,
+) -> Result<()> {
+    let track = Array1::from_shape_fn(probs.len() * chunk_size, |i| {
+        probs[i / chunk_size].clamp(0.0, 1.0)
+    });
+
+    save_audio(path, &track, sampling_rate)
+}
+
+/// Move segment boundaries to the nearest zero crossing
+/// 
+/// Cutting audio at a zero crossing avoids the click produced by an abrupt
+/// jump in the waveform. Each start and end is moved to the closest zero
+/// crossing within `search_window_ms` on either side; a boundary with no zero
+/// crossing inside the window is left unchanged.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps to adjust in place
+/// * `audio` - Audio the timestamps refer to
+/// * `sampling_rate` - Sampling rate of the audio
+/// * `search_window_ms` - Maximum distance a boundary may move
+pub fn snap_to_zero_crossings(
+    timestamps: &mut [crate::vad::SpeechTimestamps],
+    audio: &Array1<f32>,
+    sampling_rate: u32,
+    search_window_ms: u32,
+) {
+    let window = (search_window_ms as usize * sampling_rate as usize) / 1000;
+
+    let snap = |time: f32| -> f32 {
+        let target = (time * sampling_rate as f32).round() as usize;
+        if audio.len() < 2 || target > audio.len() {
+            return time;
+        }
+
+        let is_crossing = |i: usize| {
+            i > 0 && i < audio.len() && (audio[i] == 0.0 || (audio[i - 1] < 0.0) != (audio[i] < 0.0))
+        };
+
+        for offset in 0..=window {
+            if is_crossing(target.saturating_sub(offset)) {
+                return target.saturating_sub(offset) as f32 / sampling_rate as f32;
+            }
+            if is_crossing(target + offset) {
+                return (target + offset) as f32 / sampling_rate as f32;
+            }
+        }
+        time
+    };
+
+    for ts in timestamps.iter_mut() {
+        ts.start = snap(ts.start);
+        ts.end = snap(ts.end);
+    }
+}
+
+/// Rounding applied to timestamps when they are exported or printed
+/// 
+/// Every text output of the crate rounds times through a `Precision`, so a
+/// segment reads the same everywhere: [`crate::VadResult::summary_line`] and
+/// the segment log messages print with [`Precision::format`] at the default
+/// precision, and [`timestamps_to_textgrid`] rounds at the default precision
+/// but prints the shortest form, as Praat writes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    /// Round to a number of decimal places of a second
+    Decimals(u32),
+    /// Quantize to a multiple of the given number of milliseconds
+    Milliseconds(u32),
+}
+
+impl Default for Precision {
+    fn default() -> Self {
+        Precision::Decimals(3)
+    }
+}
+
+impl Precision {
+    /// Round a time in seconds
+    pub fn round(&self, seconds: f32) -> f32 {
+        match *self {
+            Precision::Decimals(places) => {
+                let scale = 10f64.powi(places as i32);
+                ((seconds as f64 * scale).round() / scale) as f32
+            }
+            Precision::Milliseconds(0) => seconds,
+            Precision::Milliseconds(step_ms) => {
+                let step = step_ms as f64 / 1000.0;
+                ((seconds as f64 / step).round() * step) as f32
+            }
+        }
+    }
+
+    /// Number of decimal places needed to print a rounded time exactly
+    pub fn decimals(&self) -> usize {
+        match *self {
+            Precision::Decimals(places) => places as usize,
+            Precision::Milliseconds(step_ms) if step_ms > 0 && step_ms % 1000 == 0 => 0,
+            Precision::Milliseconds(step_ms) if step_ms > 0 && step_ms % 100 == 0 => 1,
+            Precision::Milliseconds(step_ms) if step_ms > 0 && step_ms % 10 == 0 => 2,
+            Precision::Milliseconds(_) => 3,
+        }
+    }
+
+    /// Round a time in seconds and format it with a fixed number of decimals
+    pub fn format(&self, seconds: f32) -> String {
+        format!("{:.*}", self.decimals(), self.round(seconds))
+    }
+}
+
+/// Round the boundaries of speech segments
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps to round
+/// * `precision` - Rounding to apply to every start and end
+/// 
+/// # Returns
+/// 
+/// Segments with rounded boundaries
+pub fn round_timestamps(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    precision: Precision,
+) -> Vec<crate::vad::SpeechTimestamps> {
+    timestamps
+        .iter()
+        .map(|ts| {
+            let mut ts = ts.clone();
+            ts.start = precision.round(ts.start);
+            ts.end = precision.round(ts.end);
+            ts
+        })
+        .collect()
+}
+
+/// Compute how many windows the detector processes for a buffer
+/// 
+/// Windows of `chunk_size` samples start every `hop` samples from sample 0;
+/// only full windows are processed, so a trailing partial window is not
+/// counted. With `hop == chunk_size` this matches the non-overlapping layout
+/// used by [`crate::VADIterator::get_speech_timestamps`].
+/// 
+/// # Arguments
+/// 
+/// * `audio_len` - Length of the audio in samples
+/// * `chunk_size` - Window size in samples
+/// * `hop` - Distance between the starts of consecutive windows in samples
+/// 
+/// # Returns
+/// 
+/// Number of windows, or 0 if `chunk_size` or `hop` is zero
+pub fn chunk_count(audio_len: usize, chunk_size: usize, hop: usize) -> usize {
+    if chunk_size == 0 || hop == 0 || audio_len < chunk_size {
+        return 0;
+    }
+    (audio_len - chunk_size) / hop + 1
+}
+
+/// Apply linear fade-in and fade-out envelopes to the ends of a clip
+/// 
+/// Short fades remove the click heard when an extracted segment starts or
+/// stops abruptly. If the two fades together are longer than the clip, both
+/// are shrunk proportionally so that they meet without overlapping.
+/// 
+/// # Arguments
+/// 
+/// * `audio` - Clip to modify in place
+/// * `fade_in_ms` - Fade-in duration in milliseconds
+/// * `fade_out_ms` - Fade-out duration in milliseconds
+/// * `sampling_rate` - Sampling rate of the audio
+pub fn apply_fades(audio: &mut Array1<f32>, fade_in_ms: u32, fade_out_ms: u32, sampling_rate: u32) {
+    let len = audio.len();
+    let mut fade_in = fade_in_ms as usize * sampling_rate as usize / 1000;
+    let mut fade_out = fade_out_ms as usize * sampling_rate as usize / 1000;
+
+    if fade_in + fade_out > len {
+        let total = fade_in + fade_out;
+        fade_in = fade_in * len / total;
+        fade_out = len - fade_in;
+    }
+
+    for (i, sample) in audio.iter_mut().take(fade_in).enumerate() {
+        *sample *= i as f32 / fade_in as f32;
+    }
+    for (i, sample) in audio.iter_mut().rev().take(fade_out).enumerate() {
+        *sample *= i as f32 / fade_out as f32;
+    }
+}
+
+/// Find regions where two or more channels are in speech simultaneously
+/// 
+/// Segments within a channel that overlap each other are counted once, so
+/// only speech on different channels produces an overlap. Regions that merely
+/// touch (one channel's segment ends exactly where another's starts) are not
+/// reported.
+/// 
+/// # Arguments
+/// 
+/// * `channels` - Speech timestamps per channel
+/// 
+/// # Returns
+/// 
+/// Overlap regions in chronological order
+pub fn overlap_segments(
+    channels: &[Vec<crate::vad::SpeechTimestamps>],
+) -> Vec<crate::vad::SpeechTimestamps> {
+    // +1 when a channel enters speech, -1 when it leaves
+    let mut events: Vec<(f32, i32)> = Vec::new();
+    for segments in channels {
+        let mut sorted: Vec<_> = segments.iter().filter(|ts| ts.end > ts.start).collect();
+        sorted.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+        let mut current: Option<(f32, f32)> = None;
+        for ts in sorted {
+            current = match current {
+                Some((start, end)) if ts.start <= end => Some((start, end.max(ts.end))),
+                Some((start, end)) => {
+                    events.push((start, 1));
+                    events.push((end, -1));
+                    Some((ts.start, ts.end))
+                }
+                None => Some((ts.start, ts.end)),
+            };
+        }
+        if let Some((start, end)) = current {
+            events.push((start, 1));
+            events.push((end, -1));
+        }
+    }
+
+    // Process ends before starts at the same instant so touching segments don't overlap
+    events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    let mut result = Vec::new();
+    let mut active = 0;
+    let mut overlap_start = 0.0;
+    for (time, delta) in events {
+        let before = active;
+        active += delta;
+        if before < 2 && active >= 2 {
+            overlap_start = time;
+        } else if before >= 2 && active < 2 && time > overlap_start {
+            result.push(crate::vad::SpeechTimestamps::new(overlap_start, time));
+        }
+    }
+
+    result
+}
+
+/// Move segment boundaries inward past low-energy edges
+/// 
+/// Padding and generous silence settings often leave quiet audio at the
+/// edges of a segment. Each start is moved forward to the first sample whose
+/// magnitude exceeds `energy_floor`, and each end backward to just after the
+/// last such sample. Boundaries never move past the segment center, so a
+/// segment that is quiet throughout collapses to its center.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps to tighten
+/// * `audio` - Audio the timestamps refer to
+/// * `sampling_rate` - Sampling rate of the audio
+/// * `energy_floor` - Sample magnitude considered to be signal
+/// 
+/// # Returns
+/// 
+/// Tightened segments, in the same order
+pub fn tighten_segments(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    audio: &Array1<f32>,
+    sampling_rate: u32,
+    energy_floor: f32,
+) -> Vec<crate::vad::SpeechTimestamps> {
+    let sr = sampling_rate as f32;
+
+    timestamps
+        .iter()
+        .map(|ts| {
+            let start = ((ts.start * sr) as usize).min(audio.len());
+            let end = ((ts.end * sr) as usize).clamp(start, audio.len());
+            let center = start + (end - start) / 2;
+
+            let new_start = (start..center)
+                .find(|&i| audio[i].abs() > energy_floor)
+                .unwrap_or(center);
+            let new_end = (center..end)
+                .rev()
+                .find(|&i| audio[i].abs() > energy_floor)
+                .map(|i| i + 1)
+                .unwrap_or(center);
+
+            let mut tightened = ts.clone();
+            tightened.start = new_start as f32 / sr;
+            tightened.end = new_end.max(new_start) as f32 / sr;
+            tightened
+        })
+        .collect()
+}
+
+/// Convert a duration in milliseconds to a number of samples
+/// 
+/// # Arguments
+/// 
+/// * `ms` - Duration in milliseconds
+/// * `sampling_rate` - Sampling rate of the audio
+/// 
+/// # Returns
+/// 
+/// Number of samples, rounded to the nearest sample
+pub fn ms_to_samples(ms: u32, sampling_rate: u32) -> usize {
+    ((ms as u64 * sampling_rate as u64 + 500) / 1000) as usize
+}
+
+/// Snap speech segments outwards to a regular time grid
+/// 
+/// Starts are rounded down and ends rounded up to the nearest multiple of
+/// `grid_ms`, e.g. 40ms to align with 25fps video frames. Segments that
+/// overlap or touch once snapped are merged, with a duration-weighted
+/// confidence. A `grid_ms` of 0 returns the segments unchanged.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps in chronological order
+/// * `grid_ms` - Grid spacing in milliseconds
+/// 
+/// # Returns
+/// 
+/// Grid-aligned segments
+pub fn quantize_segments(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    grid_ms: u32,
+) -> Vec<crate::vad::SpeechTimestamps> {
+    if grid_ms == 0 {
+        return timestamps.to_vec();
+    }
+
+    let grid = grid_ms as f64 / 1000.0;
+    // Tolerate float error so a boundary already on the grid stays put
+    let steps = |t: f32| {
+        let q = t as f64 / grid;
+        if (q - q.round()).abs() < 1e-6 { q.round() } else { q }
+    };
+
+    let mut result: Vec<crate::vad::SpeechTimestamps> = Vec::new();
+    for ts in timestamps {
+        let mut snapped = ts.clone();
+        snapped.start = (steps(ts.start).floor() * grid) as f32;
+        snapped.end = (steps(ts.end).ceil() * grid) as f32;
+
+        if let Some(current) = result.last_mut() {
+            if snapped.start <= current.end {
+                let current_len = current.end - current.start;
+                let snapped_len = snapped.end - snapped.start;
+                if current_len + snapped_len > 0.0 {
+                    current.confidence = (current.confidence * current_len
+                        + snapped.confidence * snapped_len)
+                        / (current_len + snapped_len);
+                }
+                current.peak_confidence = current.peak_confidence.max(snapped.peak_confidence);
+                current.end = current.end.max(snapped.end);
+                continue;
+            }
+        }
+        result.push(snapped);
+    }
+
+    result
+}
+
+/// Fraction of samples at or above a clipping level
+/// 
+/// # Arguments
+/// 
+/// * `audio` - Audio to inspect
+/// * `level` - Magnitude from which a sample counts as clipped, e.g. 0.99
+/// 
+/// # Returns
+/// 
+/// The clipped fraction in `[0.0, 1.0]` (0.0 for empty audio)
+pub fn clipping_ratio(audio: &ArrayView1<f32>, level: f32) -> f32 {
+    if audio.is_empty() {
+        return 0.0;
+    }
+    audio.iter().filter(|x| x.abs() >= level).count() as f32 / audio.len() as f32
+}
+
+/// Drop segments that begin on a clipping event rather than on speech
+/// 
+/// The first 32ms of every segment is checked with [`clipping_ratio`] at a
+/// level of 0.99; a segment is dropped when at least 1% of those samples are
+/// clipped. Hard-clipped transients that trigger the detector are removed
+/// this way, but so is genuine speech that starts in overload.
+/// 
+/// # Arguments
+/// 
+/// * `timestamps` - Speech timestamps detected in `audio`
+/// * `audio` - Complete audio data
+/// * `sampling_rate` - Sampling rate of the audio
+/// 
+/// # Returns
+/// 
+/// The segments whose onset is not clipped
+pub fn suppress_clipped_onsets(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    audio: &ArrayView1<f32>,
+    sampling_rate: u32,
+) -> Vec<crate::vad::SpeechTimestamps> {
+    let onset_len = ms_to_samples(32, sampling_rate);
+    timestamps
+        .iter()
+        .filter(|ts| !onset_is_clipped(audio, sample_range(ts, sampling_rate), onset_len))
+        .cloned()
+        .collect()
+}
+
+/// Drop segments with a clipped onset, locating them by their sample positions
+/// 
+/// Same as [`suppress_clipped_onsets`], but reads the onset from
+/// [`crate::SpeechTimestamps::start_sample`] and
+/// [`crate::SpeechTimestamps::end_sample`], which stay exact when reported
+/// times are scaled.
+pub(crate) fn suppress_clipped_onsets_by_sample(
+    timestamps: &[crate::vad::SpeechTimestamps],
+    audio: &ArrayView1<f32>,
+    sampling_rate: u32,
+) -> Vec<crate::vad::SpeechTimestamps> {
+    let onset_len = ms_to_samples(32, sampling_rate);
+    timestamps
+        .iter()
+        .filter(|ts| !onset_is_clipped(audio, ts.start_sample..ts.end_sample, onset_len))
+        .cloned()
+        .collect()
+}
+
+/// Whether at least 1% of the first `onset_len` samples of a range are clipped
+fn onset_is_clipped(audio: &ArrayView1<f32>, range: std::ops::Range<usize>, onset_len: usize) -> bool {
+    const CLIP_LEVEL: f32 = 0.99;
+    const MAX_CLIPPED: f32 = 0.01;
+    let start = range.start.min(audio.len());
+    let end = (start + onset_len).min(range.end).min(audio.len()).max(start);
+    clipping_ratio(&audio.slice(s![start..end]), CLIP_LEVEL) >= MAX_CLIPPED
+}
+
+/// Estimate the signal-to-noise ratio of a recording from its speech segments
+/// 
+/// Samples inside the segments are treated as signal and all remaining
+/// samples as noise; the result is the ratio of their RMS levels in dB.
+/// Digitally silent noise yields `f32::INFINITY`.
+/// 
+/// # Arguments
+/// 
+/// * `audio` - Audio data
+/// * `timestamps` - Speech segments detected in `audio`
+/// * `sampling_rate` - Sampling rate of the audio
+/// 
+/// # Returns
+/// 
+/// Estimated SNR in dB
+/// 
+/// # Errors
+/// 
+/// Returns an error if the segments cover none or all of the audio, since
+/// either the signal or the noise level cannot be measured then
+pub fn estimate_snr(
+    audio: &Array1<f32>,
+    timestamps: &[crate::vad::SpeechTimestamps],
+    sampling_rate: u32,
+) -> Result<f32> {
+    let sr = sampling_rate as f32;
+    let mut is_speech = vec![false; audio.len()];
+    for ts in timestamps {
+        let start = ((ts.start.max(0.0) * sr) as usize).min(audio.len());
+        let end = ((ts.end.max(0.0) * sr) as usize).min(audio.len());
+        if end > start {
+            is_speech[start..end].fill(true);
+        }
+    }
+
+    let (mut speech_energy, mut speech_count) = (0.0f64, 0usize);
+    let (mut noise_energy, mut noise_count) = (0.0f64, 0usize);
+    for (&sample, &speech) in audio.iter().zip(&is_speech) {
+        let energy = sample as f64 * sample as f64;
+        if speech {
+            speech_energy += energy;
+            speech_count += 1;
+        } else {
+            noise_energy += energy;
+            noise_count += 1;
+        }
+    }
+
+    if speech_count == 0 || noise_count == 0 {
+        return Err(Error::InvalidInput(format!(
+            "Cannot estimate SNR from {} speech and {} non-speech sample(s); both are required",
+            speech_count, noise_count
+        )));
+    }
+
+    let speech_power = speech_energy / speech_count as f64;
+    let noise_power = noise_energy / noise_count as f64;
+    if noise_power == 0.0 {
+        return Ok(f32::INFINITY);
+    }
+    // The power ratio in dB equals 20 * log10 of the RMS ratio
+    Ok((10.0 * (speech_power / noise_power).log10()) as f32)
+}
+
+/// Reduce multi-channel audio to a single mono channel
+/// 
+/// # Arguments
+/// 
+/// * `audio` - Audio with shape `[channels, samples]`
+/// * `mode` - [`ChannelMode::Downmix`] averages the channels and
+///   [`ChannelMode::Select`] keeps one of them
+/// 
+/// # Returns
+/// 
+/// Mono audio data
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// * The audio has no channels
+/// * The selected channel doesn't exist
+/// * `mode` is [`ChannelMode::Keep`], which does not produce mono audio
+pub fn downmix_channels(audio: &ArrayView2<f32>, mode: ChannelMode) -> Result<Array1<f32>> {
+    let channels = audio.nrows();
+    if channels == 0 {
+        return Err(Error::InvalidInput("Audio has no channels".into()));
+    }
+
+    match mode {
+        ChannelMode::Downmix => Ok(audio.sum_axis(Axis(0)) / channels as f32),
+        ChannelMode::Select(channel) if (channel as usize) < channels => {
+            Ok(audio.row(channel as usize).to_owned())
+        }
+        ChannelMode::Select(channel) => Err(Error::InvalidInput(format!(
+            "Channel {} selected but the audio only has {} channel(s)",
+            channel, channels
+        ))),
+        ChannelMode::Keep => Err(Error::InvalidInput(
+            "ChannelMode::Keep cannot reduce audio to mono; use Downmix or Select".into(),
+        )),
+    }
+}
+
+/// Average planar (non-interleaved) stereo channels into mono audio
+/// 
+/// # Arguments
+/// 
+/// * `left` - Samples of the left channel
+/// * `right` - Samples of the right channel
+/// 
+/// # Returns
+/// 
+/// Mono audio data
+/// 
+/// # Errors
+/// 
+/// Returns an error if the channels have different lengths
+pub fn downmix_planar(left: &[f32], right: &[f32]) -> Result<Array1<f32>> {
+    if left.len() != right.len() {
+        return Err(Error::InvalidInput(format!(
+            "Planar channels differ in length: left has {} samples, right has {}",
+            left.len(),
+            right.len()
+        )));
+    }
+
+    Ok(left.iter().zip(right).map(|(l, r)| (l + r) / 2.0).collect())
+}
+
+/// Sample indices covered by a segment in a buffer at the given rate
+/// 
+/// Timestamps are in seconds and therefore rate-independent, but sample
+/// indices are not: the same segment spans different indices in an 8kHz and
+/// a 16kHz buffer. Always pass the rate of the buffer the range will slice.
+/// Boundaries are truncated to whole samples, as in [`collect_chunks`].
+/// 
+/// # Arguments
+/// 
+/// * `timestamp` - Speech segment in seconds
+/// * `sampling_rate` - Sampling rate of the buffer to index
+/// 
+/// # Returns
+/// 
+/// Half-open range of sample indices
+pub fn sample_range(
+    timestamp: &crate::vad::SpeechTimestamps,
+    sampling_rate: u32,
+) -> std::ops::Range<usize> {
+    let sr = sampling_rate as f32;
+    let start = (timestamp.start.max(0.0) * sr) as usize;
+    let end = (timestamp.end.max(0.0) * sr) as usize;
+    start..end
+}
+
+/// Re-express a sample index against a buffer at a different rate
+/// 
+/// # Arguments
+/// 
+/// * `index` - Sample index in the buffer at `from_rate`
+/// * `from_rate` - Sampling rate the index refers to
+/// * `to_rate` - Sampling rate of the target buffer
+/// 
+/// # Returns
+/// 
+/// The index of the same instant at `to_rate`, rounded to the nearest sample
+pub fn convert_sample_index(index: usize, from_rate: u32, to_rate: u32) -> usize {
+    if from_rate == 0 {
+        return 0;
+    }
+    ((index as u64 * to_rate as u64 + from_rate as u64 / 2) / from_rate as u64) as usize
+}
+
+/// Re-express a range of sample indices against a buffer at a different rate
+/// 
+/// E.g. the range `800..1600` of an 8kHz buffer becomes `1600..3200` at 16kHz.
+/// 
+/// # Arguments
+/// 
+/// * `range` - Sample range in the buffer at `from_rate`
+/// * `from_rate` - Sampling rate the range refers to
+/// * `to_rate` - Sampling rate of the target buffer
+/// 
+/// # Returns
+/// 
+/// The range covering the same span at `to_rate`
+pub fn convert_sample_range(
+    range: std::ops::Range<usize>,
+    from_rate: u32,
+    to_rate: u32,
+) -> std::ops::Range<usize> {
+    let start = convert_sample_index(range.start, from_rate, to_rate);
+    let end = convert_sample_index(range.end, from_rate, to_rate);
+    start..end
+}
+
+/// Soft-limit audio peaks above a threshold
+/// 
+/// Samples with a magnitude up to `threshold` are left unchanged. Above it, a
+/// soft knee compresses the excess with a `tanh` curve that approaches full
+/// scale asymptotically, so peaks are tamed without the harsh corners of
+/// hard clipping. A threshold outside `(0.0, 1.0)` leaves the audio unchanged.
+/// 
+/// # Arguments
+/// 
+/// * `audio` - Audio data to limit in place
+/// * `threshold` - Magnitude where limiting starts
+pub fn soft_limit(audio: &mut Array1<f32>, threshold: f32) {
+    audio.mapv_inplace(|v| soft_limit_sample(v, threshold));
+}
+
+/// Soft-limit a single sample, see [`soft_limit`]
+pub(crate) fn soft_limit_sample(sample: f32, threshold: f32) -> f32 {
+    let magnitude = sample.abs();
+    if !(0.0..1.0).contains(&threshold) || threshold == 0.0 || magnitude <= threshold {
+        return sample;
+    }
+    let headroom = 1.0 - threshold;
+    let limited = threshold + headroom * ((magnitude - threshold) / headroom).tanh();
+    limited.copysign(sample)
+}
+
+/// Run-length encode a sequence of per-window speech decisions
+/// 
+/// # Arguments
+/// 
+/// * `decisions` - Speech decision of each window, in order
+/// 
+/// # Returns
+/// 
+/// `(is_speech, window_count)` runs in order; consecutive runs always differ
+pub fn rle_encode(decisions: &[bool]) -> Vec<(bool, u32)> {
+    let mut runs: Vec<(bool, u32)> = Vec::new();
+    for &decision in decisions {
+        match runs.last_mut() {
+            Some((speech, count)) if *speech == decision => *count += 1,
+            _ => runs.push((decision, 1)),
+        }
+    }
+    runs
+}
+
+/// Expand run-length encoded speech decisions back into one decision per window
+/// 
+/// # Arguments
+/// 
+/// * `runs` - `(is_speech, window_count)` runs, as produced by [`rle_encode`]
+/// 
+/// # Returns
+/// 
+/// Speech decision of each window, in order
+pub fn rle_decode(runs: &[(bool, u32)]) -> Vec<bool> {
+    runs.iter()
+        .flat_map(|&(speech, count)| std::iter::repeat(speech).take(count as usize))
+        .collect()
+}
+
+/// Suggest detector parameters for a recording without running the model
+/// 
+/// A quick starting point for new users, better suited to the recording than
+/// the fixed 100ms/250ms defaults. Each 32ms window is scored by its energy
+/// relative to the quiet and loud ends of the recording, and the pause and
+/// burst lengths of that energy track are analyzed as in
+/// [`crate::suggest_parameters_from_probs`]. The suggested minimum speech
+/// duration is carried as [`crate::VadConfig::min_speech_samples`]. Energy
+/// does not map onto model probabilities, so the threshold keeps its
+/// default; [`crate::VADIterator::suggest_parameters`] also tunes it with a
+/// model pass.
+/// 
+/// # Arguments
+/// 
+/// * `audio` - Representative audio, ideally several seconds of speech
+/// * `sampling_rate` - Sampling rate of the audio
+/// 
+/// # Returns
+/// 
+/// Detector configuration with the suggested durations
+pub fn suggest_parameters(audio: &ArrayView1<f32>, sampling_rate: u32) -> crate::VadConfig {
+    let config = crate::VadConfig { sampling_rate, ..Default::default() };
+    let window = crate::vad::window_size_for(sampling_rate);
+    if window == 0 {
+        return config;
+    }
+
+    // Window levels in dB, floored so digital silence doesn't dominate
+    let levels: Vec<f32> = audio
+        .exact_chunks(window)
+        .into_iter()
+        .map(|chunk| {
+            let power = chunk.iter().map(|&v| v * v).sum::<f32>() / window as f32;
+            10.0 * power.max(1e-10).log10()
+        })
+        .collect();
+    let mut sorted = levels.clone();
+    sorted.sort_unstable_by(f32::total_cmp);
+    let (quiet, loud) = match (sorted.get(sorted.len() / 10), sorted.get(sorted.len() * 9 / 10)) {
+        (Some(&quiet), Some(&loud)) if loud - quiet > 1.0 => (quiet, loud),
+        _ => return config,
+    };
+    let probs: Vec<f32> = levels
+        .iter()
+        .map(|level| ((level - quiet) / (loud - quiet)).clamp(0.0, 1.0))
+        .collect();
+
+    let suggested = crate::suggest_parameters_from_probs(&probs, &config);
+    crate::VadConfig {
+        min_silence_duration_ms: suggested.config.min_silence_duration_ms,
+        min_speech_samples: Some(ms_to_samples(suggested.min_speech_duration_ms, sampling_rate)),
+        ..config
+    }
+}
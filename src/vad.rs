@@ -3,10 +3,27 @@
 //! This module provides the VAD iterator for processing audio streams and detecting speech segments.
 //! It handles both streaming and batch processing of audio data.
 
-use crate::{Result, SileroVAD};
-use ndarray::{ArrayView1, Array2};
+use crate::{events, model::CONTEXT_SIZE, utils::ChannelMode, Error, Result, SileroVAD};
+use ndarray::{Array1, ArrayView1, Array2, ArrayView2};
 use serde::{Deserialize, Serialize};
 use log::debug;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+/// What delimits a speech segment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplitReason {
+    /// The segment is bounded by real silence
+    #[default]
+    Silence,
+    /// The segment runs longer than the maximum speech duration
+    /// 
+    /// Either continuous speech kept whole (see
+    /// [`VadConfig::keep_long_segments`]) or a piece cut at an artificial
+    /// boundary rather than at a pause (see [`crate::utils::rechunk_segments`]).
+    MaxDuration,
+}
 
 /// Speech timestamp information
 /// 
@@ -18,6 +35,847 @@ pub struct SpeechTimestamps {
     pub start: f32,
     /// End time in seconds
     pub end: f32,
+    /// Mean speech probability over the segment's windows
+    #[serde(default)]
+    pub confidence: f32,
+    /// Highest speech probability reached within the segment
+    /// 
+    /// Unlike the mean, this is not pulled down by tapering edges, so it
+    /// tells whether the segment ever held a strong speech signal.
+    #[serde(default)]
+    pub peak_confidence: f32,
+    /// Whether the segment's extent follows real pauses or the maximum speech duration
+    #[serde(default)]
+    pub split_reason: SplitReason,
+    /// First sample of the segment, counted from the start of the stream
+    /// 
+    /// Set by the detector alongside `start`, unaffected by
+    /// [`VadConfig::time_scale`] and free of float rounding. Helpers that
+    /// move `start` in seconds leave it unchanged.
+    #[serde(default)]
+    pub start_sample: usize,
+    /// Sample just past the end of the segment, counted like `start_sample`
+    #[serde(default)]
+    pub end_sample: usize,
+}
+
+impl SpeechTimestamps {
+    /// Create a segment with unknown confidence (0.0)
+    pub fn new(start: f32, end: f32) -> Self {
+        Self {
+            start,
+            end,
+            confidence: 0.0,
+            peak_confidence: 0.0,
+            split_reason: SplitReason::Silence,
+            start_sample: 0,
+            end_sample: 0,
+        }
+    }
+
+    /// Length of the segment in samples, see [`SpeechTimestamps::start_sample`]
+    pub fn sample_len(&self) -> usize {
+        self.end_sample.saturating_sub(self.start_sample)
+    }
+}
+
+impl VadConfig {
+    /// Create a default configuration matching the sampling rate of a WAV file
+    /// 
+    /// Picking the rate from the file keeps the detector's window size in
+    /// line with the audio that will be fed to it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Path to the WAV file that will be analyzed
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The file cannot be read
+    /// * The file's sampling rate is not supported by the model; resample it
+    ///   with [`crate::utils::read_audio_opts`]
+    pub fn for_wav<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let sampling_rate = crate::utils::wav_sampling_rate(path)?;
+        crate::model::check_sampling_rate(sampling_rate)?;
+        Ok(Self {
+            sampling_rate,
+            ..Default::default()
+        })
+    }
+
+    /// Create a configuration tuned for short voice commands
+    /// 
+    /// Voice-command apps care about single short words ("yes", "stop") that
+    /// the defaults, tuned for dictation, tend to drop. The preset:
+    /// 
+    /// * lowers the threshold to 0.4 for higher sensitivity
+    /// * closes segments after 60ms of silence for low latency
+    /// * keeps segments of at least 64ms (two windows) through
+    ///   `min_speech_samples`, overriding the `min_speech_duration_ms`
+    ///   argument of the offline detectors
+    /// * ends utterances after 300ms in [`VADIterator::poll_endpoint`]
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sampling_rate` - Sampling rate of the audio
+    pub fn command_mode(sampling_rate: u32) -> Self {
+        Self {
+            threshold: 0.4,
+            sampling_rate,
+            min_silence_duration_ms: 60,
+            min_speech_samples: Some(crate::utils::ms_to_samples(64, sampling_rate)),
+            endpoint_silence_ms: 300,
+            ..Default::default()
+        }
+    }
+
+    /// Get the detection threshold applied at the configured sampling rate
+    /// 
+    /// # Returns
+    /// 
+    /// The `rate_thresholds` entry for `sampling_rate`, or `threshold` if there is none
+    pub fn effective_threshold(&self) -> f32 {
+        self.rate_thresholds
+            .get(&self.sampling_rate)
+            .copied()
+            .unwrap_or(self.threshold)
+    }
+
+    /// Worst-case delay between the real end of speech and the emission of its segment
+    /// 
+    /// Streaming detection only closes a segment after enough silence:
+    /// 
+    /// * up to one window passes before the window holding the end of speech
+    ///   is complete
+    /// * `hangover_ms` keeps the segment open, rounded up to whole windows
+    /// * `min_silence_duration_ms` must then pass, rounded up to whole
+    ///   windows and at least one window
+    /// 
+    /// A `turn_silence_ms` shorter than the sum of the last two closes the
+    /// segment sooner. Padding only moves the reported boundary and adds no
+    /// delay; `gap_fill_windows` and `half_window_refinement` only apply to
+    /// offline detection.
+    /// 
+    /// # Returns
+    /// 
+    /// The latency in milliseconds, rounded up
+    pub fn emission_latency_ms(&self) -> u32 {
+        let window = window_size_for(self.sampling_rate).max(1);
+        let windows = |ms: u32| {
+            crate::utils::ms_to_samples(ms, self.sampling_rate)
+                .div_ceil(window)
+                .max(1)
+        };
+
+        let hangover = if self.hangover_ms > 0 { windows(self.hangover_ms) } else { 0 };
+        let mut closing = hangover + windows(self.min_silence_duration_ms);
+        if self.turn_silence_ms > 0 {
+            closing = closing.min(windows(self.turn_silence_ms));
+        }
+
+        let samples = ((1 + closing) * window) as u64;
+        let rate = self.sampling_rate.max(1) as u64;
+        (samples * 1000).div_ceil(rate) as u32
+    }
+
+    /// Apply the detection threshold to a window probability
+    fn is_speech(&self, prob: f32) -> bool {
+        if self.inclusive_threshold {
+            prob >= self.effective_threshold()
+        } else {
+            prob > self.effective_threshold()
+        }
+    }
+
+    /// Whether a pause of `silence_samples` is long enough to split at a turn
+    fn is_turn(&self, silence_samples: usize) -> bool {
+        self.turn_silence_ms > 0
+            && silence_samples
+                >= crate::utils::ms_to_samples(self.turn_silence_ms, self.sampling_rate)
+    }
+
+    /// Fill short dropouts inside speech in a sequence of window probabilities
+    /// 
+    /// Every run of at most `gap_fill_windows` sub-threshold windows with a
+    /// speech window on both sides is raised to the smaller of the two
+    /// neighbouring probabilities, so the run is treated as speech. Leading
+    /// and trailing runs are never filled, nor are runs long enough to count
+    /// as a turn under `turn_silence_ms`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `probs` - Window probabilities, modified in place
+    pub fn fill_gaps(&self, probs: &mut [f32]) {
+        if self.gap_fill_windows == 0 {
+            return;
+        }
+
+        let speech: Vec<usize> = probs
+            .iter()
+            .enumerate()
+            .filter(|(_, &p)| self.is_speech(p))
+            .map(|(i, _)| i)
+            .collect();
+
+        let window_size = window_size_for(self.sampling_rate);
+        for pair in speech.windows(2) {
+            let (prev, next) = (pair[0], pair[1]);
+            let gap = next - prev - 1;
+            if gap > 0 && gap <= self.gap_fill_windows && !self.is_turn(gap * window_size) {
+                let fill = probs[prev].min(probs[next]);
+                probs[prev + 1..next].fill(fill);
+            }
+        }
+    }
+}
+
+/// Intermediate results of offline detection, see [`VADIterator::detect_detailed`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetectionDetail {
+    /// Raw speech probability of each window
+    pub probabilities: Vec<f32>,
+    /// Whether each window was above the threshold, before any post-processing
+    pub triggers: Vec<bool>,
+    /// Runs of consecutive triggered windows, without gap filling, hangover,
+    /// silence merging or padding
+    pub initial_segments: Vec<SpeechTimestamps>,
+    /// Segments after all post-processing, as the detector reports them
+    pub final_segments: Vec<SpeechTimestamps>,
+}
+
+/// Cached probability pass of a clip, see [`VADIterator::dump_analysis`]
+/// 
+/// Stores everything needed to re-run segmentation without the model, so
+/// segmentation parameters can be tuned on the CPU with
+/// [`segment_from_analysis`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbabilityAnalysis {
+    /// Sampling rate of the analyzed audio
+    pub sampling_rate: u32,
+    /// Window size in samples the probabilities were computed on
+    pub window_size: usize,
+    /// Number of samples of the analyzed audio
+    pub audio_samples: usize,
+    /// Speech probability of each window, in order
+    pub probabilities: Vec<f32>,
+    /// Configuration of the detector that produced the probabilities
+    pub config: VadConfig,
+}
+
+impl ProbabilityAnalysis {
+    /// Write the analysis to a JSON file
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the file cannot be written
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        serde_json::to_writer(file, self).map_err(std::io::Error::from)?;
+        Ok(())
+    }
+
+    /// Read an analysis written by [`ProbabilityAnalysis::save`]
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the file cannot be read or is not a valid analysis
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        serde_json::from_reader(file)
+            .map_err(|e| Error::InvalidInput(format!("Invalid analysis file: {}", e)))
+    }
+}
+
+/// A detected segment bundled with its audio, see [`VADIterator::detect_with_audio`]
+#[derive(Debug, Clone)]
+pub struct SegmentWithAudio {
+    /// Segment boundaries
+    pub timestamps: SpeechTimestamps,
+    /// Samples of the segment, copied from the analyzed audio
+    pub audio: Array1<f32>,
+}
+
+/// Speech boundary event reported while streaming
+/// 
+/// Serializes as a flat JSON object tagged by `event`, e.g.
+/// `{"event":"speech_start","timestamp":1.024,"probability":0.93}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum VadEvent {
+    /// A speech segment opened
+    SpeechStart {
+        /// Segment start in seconds
+        timestamp: f32,
+        /// Probability of the window that opened the segment
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        probability: Option<f32>,
+    },
+    /// A speech segment is still open; reported on every chunk after the
+    /// one that opened it until it closes
+    SpeechOngoing {
+        /// Segment start in seconds
+        start: f32,
+        /// End of the latest speech window in seconds, without padding
+        current_end: f32,
+    },
+    /// A speech segment closed
+    SpeechEnd {
+        /// Segment end in seconds, including padding
+        timestamp: f32,
+        /// Mean probability over the segment
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        probability: Option<f32>,
+    },
+}
+
+impl VadEvent {
+    /// Write the event as a single JSON Lines record
+    /// 
+    /// The event is serialized as one JSON object followed by a newline, so a
+    /// stream of events can be piped to another process and read line by line.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if writing fails
+    pub fn write_json_line<W: Write>(&self, mut writer: W) -> Result<()> {
+        serde_json::to_writer(&mut writer, self).map_err(std::io::Error::from)?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+/// Cumulative statistics of a stream, see [`VADIterator::running_stats`]
+/// 
+/// Durations follow the reported timeline, so they honour
+/// [`VadConfig::time_scale`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct VadStats {
+    /// Total time inside speech segments, including hangover, in seconds
+    pub speech_duration_s: f32,
+    /// Total time outside speech segments in seconds
+    pub silence_duration_s: f32,
+    /// Number of finalized speech segments
+    pub segment_count: usize,
+}
+
+impl VadStats {
+    /// Fraction of the processed audio that was speech (0.0 before any audio)
+    pub fn speech_ratio(&self) -> f32 {
+        let total = self.speech_duration_s + self.silence_duration_s;
+        if total > 0.0 {
+            self.speech_duration_s / total
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Detection result for one audio file, see [`VADIterator::detect_file`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VadResult {
+    /// File the audio was read from
+    pub path: std::path::PathBuf,
+    /// Duration of the audio in seconds
+    pub duration_s: f32,
+    /// Detected speech segments
+    pub segments: Vec<SpeechTimestamps>,
+}
+
+impl VadResult {
+    /// Total duration of the speech segments in seconds
+    pub fn speech_duration_s(&self) -> f32 {
+        self.segments.iter().map(|ts| ts.end - ts.start).fold(0.0, |total, d| total + d)
+    }
+
+    /// Fraction of the file that is speech (0.0 for an empty file)
+    pub fn speech_ratio(&self) -> f32 {
+        if self.duration_s > 0.0 {
+            self.speech_duration_s() / self.duration_s
+        } else {
+            0.0
+        }
+    }
+
+    /// Highest speech probability reached in any segment (0.0 without segments)
+    pub fn peak_confidence(&self) -> f32 {
+        self.segments.iter().map(|ts| ts.peak_confidence).fold(0.0, f32::max)
+    }
+
+    /// Format the result as a one-line log record
+    /// 
+    /// The line holds space-separated `key=value` fields in a fixed order:
+    /// 
+    /// `path="<path>" duration=<s> segments=<count> speech=<s> ratio=<ratio> peak=<prob>`
+    /// 
+    /// The path is quoted and escaped like a Rust string literal, so it may
+    /// contain spaces; durations are in seconds, rounded with the default
+    /// [`crate::utils::Precision`], and all numbers have three decimals. For
+    /// example:
+    /// 
+    /// `path="calls/a.wav" duration=12.500s segments=3 speech=4.250s ratio=0.340 peak=0.981`
+    pub fn summary_line(&self) -> String {
+        let precision = crate::utils::Precision::default();
+        format!(
+            "path={:?} duration={}s segments={} speech={}s ratio={:.3} peak={:.3}",
+            self.path.display().to_string(),
+            precision.format(self.duration_s),
+            self.segments.len(),
+            precision.format(self.speech_duration_s()),
+            self.speech_ratio(),
+            self.peak_confidence()
+        )
+    }
+}
+
+/// Endpointing state reported by [`VADIterator::poll_endpoint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EndpointState {
+    /// Waiting for the user to start talking
+    Listening,
+    /// An utterance is in progress, possibly in a pause shorter than the
+    /// configured trailing silence
+    Speaking,
+    /// The utterance just ended; reported once, then back to `Listening`
+    Endpointed,
+}
+
+/// Coarse classification of a whole clip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClipClass {
+    /// Most of the clip is speech
+    MostlySpeech,
+    /// The clip contains little or no speech
+    MostlySilence,
+    /// The detector is undecided, e.g. music or heavy noise
+    Uncertain,
+}
+
+/// Detector parameters suggested for a recording, see [`VADIterator::suggest_parameters`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuggestedParameters {
+    /// Configuration with the suggested threshold and minimum silence
+    pub config: VadConfig,
+    /// Suggested `min_speech_duration_ms` for [`VADIterator::get_speech_timestamps`]
+    pub min_speech_duration_ms: u32,
+}
+
+/// Detector configuration
+/// 
+/// Holds every setting that influences detection, so that the exact settings
+/// used to produce a result can be stored next to it and reloaded later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VadConfig {
+    /// Speech detection threshold (0.0 to 1.0)
+    /// 
+    /// Used at every sampling rate without an entry in `rate_thresholds`.
+    pub threshold: f32,
+    /// Speech detection thresholds for specific sampling rates
+    /// 
+    /// The optimal threshold can differ between e.g. 8kHz telephony and
+    /// 16kHz audio. The entry for `sampling_rate`, if any, takes precedence
+    /// over `threshold`; see [`VadConfig::effective_threshold`]. The reference
+    /// Silero setup uses the same threshold at every rate, so no entries are
+    /// set by default.
+    pub rate_thresholds: BTreeMap<u32, f32>,
+    /// Audio sampling rate
+    pub sampling_rate: u32,
+    /// Minimum silence duration to end speech segment
+    pub min_silence_duration_ms: u32,
+    /// Padding to add to speech segments
+    pub speech_pad_ms: u32,
+    /// Index of the model output class to threshold against
+    /// 
+    /// Single-output models only provide class 0, the speech probability.
+    pub class_index: usize,
+    /// Time the speech decision is held after the probability drops below
+    /// the threshold, bridging brief unvoiced sounds inside words
+    pub hangover_ms: u32,
+    /// Longest run of sub-threshold windows between speech windows that is
+    /// filled in as speech during offline detection (0 disables gap filling)
+    pub gap_fill_windows: usize,
+    /// Fewest whole windows offline detection accepts before reporting an
+    /// error, since shorter clips give the model too little context to be
+    /// reliable (0, the default, disables the check)
+    pub min_audio_windows: usize,
+    /// Minimum speech segment length in samples
+    /// 
+    /// When set, takes precedence over the `min_speech_duration_ms` argument of
+    /// the offline detectors, giving sample-exact, rounding-free control.
+    pub min_speech_samples: Option<usize>,
+    /// Maximum speech segment length in samples
+    /// 
+    /// When set, takes precedence over the `max_speech_duration_s` argument of
+    /// the offline detectors.
+    pub max_speech_samples: Option<usize>,
+    /// Level above which samples are soft-limited before inference, taming
+    /// loud transients the model over-triggers on (`None` disables limiting)
+    pub limiter_threshold: Option<f32>,
+    /// Trailing silence after speech that ends an utterance in
+    /// [`VADIterator::poll_endpoint`]
+    pub endpoint_silence_ms: u32,
+    /// Samples of the previous chunk used to refine onsets in chunk-by-chunk
+    /// processing (0 disables the lookback)
+    /// 
+    /// When a chunk opens a segment, an extra window straddling the chunk
+    /// boundary is scored; if it is speech too, the onset is moved back by
+    /// this many samples. Onsets near a boundary are then reported up to
+    /// `lookback_samples` earlier instead of a whole chunk late, at the cost
+    /// of one extra inference per onset. Must be smaller than the window.
+    pub lookback_samples: usize,
+    /// Pause that always splits a segment, however the pause is otherwise
+    /// bridged (0 disables turn splitting)
+    /// 
+    /// Set above `min_silence_duration_ms` together with `hangover_ms` or
+    /// `gap_fill_windows` to keep word gaps joined while still breaking
+    /// segments at likely speaker turns, as wanted for diarization.
+    pub turn_silence_ms: u32,
+    /// Factor applied to every reported time (1.0 leaves times unchanged)
+    /// 
+    /// Maps times from the timeline of the audio fed to the detector onto
+    /// the caller's, e.g. when the samples of a source at another rate were
+    /// fed without resampling. Durations in the configuration (silences,
+    /// padding, speech limits) keep referring to the fed audio. Audio
+    /// resampled with [`crate::utils::resample`] needs no scaling: its times
+    /// in seconds are unchanged, and [`crate::utils::sample_range`] at the
+    /// original rate gives its sample positions in the source. Must be
+    /// positive and finite.
+    pub time_scale: f32,
+    /// Run a second, half-window-offset pass in offline detection to halve
+    /// the alignment-dependent jitter of reported boundaries
+    /// 
+    /// Windows always start at multiples of the window size, so where an
+    /// onset falls relative to them is arbitrary and shifts reported onsets
+    /// by up to a window. With this option the audio is also scored on a grid
+    /// offset by half a window and both passes are combined, which roughly
+    /// doubles the inference cost of [`VADIterator::get_speech_timestamps`].
+    /// `gap_fill_windows` then counts half windows.
+    pub half_window_refinement: bool,
+    /// Drop segments of offline detection that begin on a clipping event,
+    /// see [`crate::utils::suppress_clipped_onsets`]
+    /// 
+    /// Hard-clipped transients (overloaded knocks, pops) can trigger the
+    /// model although they aren't speech. Genuine speech that starts clipped
+    /// is dropped as well, so enable this only for recordings with overload.
+    pub suppress_clipped_onsets: bool,
+    /// Lead-in at the start of a stream that is never treated as speech
+    /// 
+    /// Streams often open with a connection click or a burst of noise. Windows
+    /// starting within this many milliseconds of the start of the timeline
+    /// are scored as silence, so they cannot open a segment, but still count
+    /// towards the timeline. Unlike audio passed to [`VADIterator::prime`],
+    /// the lead-in is part of the stream. 0 disables the skip.
+    pub initial_skip_ms: u32,
+    /// Whether a probability exactly at the threshold counts as speech
+    /// 
+    /// `true` (the default) compares with `>=`; `false` compares with a
+    /// strict `>`, as some other implementations do, for bit-exact
+    /// reproduction of their results. Applies to streaming and offline
+    /// detection alike.
+    pub inclusive_threshold: bool,
+    /// Treat consecutive [`VADIterator::get_speech_timestamps`] calls as
+    /// pieces of one stream
+    /// 
+    /// The model context is kept when a segment is emitted and samples that
+    /// do not fill a whole window are carried into the next call, so reading
+    /// a long file in pieces gives the same segments as processing it whole.
+    /// Timestamps are absolute from the start of the stream and
+    /// [`VADIterator::reset`] starts a new one. The offline-only refinements
+    /// (`gap_fill_windows`, `half_window_refinement` and
+    /// `suppress_clipped_onsets`) need the whole clip and are not applied.
+    pub continuous_file: bool,
+    /// Number of recent window probabilities kept for debugging, see
+    /// [`VADIterator::recent_probabilities`] (0 disables the history)
+    pub probability_history: usize,
+    /// Keep segments longer than the maximum speech duration in offline
+    /// detection instead of dropping them
+    /// 
+    /// Long monologues or read speech without pauses are then reported whole,
+    /// flagged with [`SplitReason::MaxDuration`] so consumers can treat them
+    /// specially.
+    pub keep_long_segments: bool,
+    /// Score the audio forward and in reverse in offline detection and
+    /// average the two probability curves
+    /// 
+    /// The model's context builds up forward in time, so speech ends tend to
+    /// be detected late; the reversed pass has the opposite bias and the
+    /// average places onsets and offsets more symmetrically. Offline only,
+    /// since the reversed pass needs the whole buffer, and it roughly doubles
+    /// the inference cost of [`VADIterator::get_speech_timestamps`]. Takes
+    /// precedence over `half_window_refinement`.
+    /// 
+    /// As in the forward-only path, a trailing partial window is not scored
+    /// and a segment still open at the end of the buffer is not reported;
+    /// pad the audio with silence to close a segment running to its end.
+    pub bidirectional: bool,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            rate_thresholds: BTreeMap::new(),
+            sampling_rate: 16000,
+            min_silence_duration_ms: 100,
+            speech_pad_ms: 30,
+            class_index: 0,
+            hangover_ms: 0,
+            gap_fill_windows: 0,
+            min_audio_windows: 0,
+            min_speech_samples: None,
+            max_speech_samples: None,
+            limiter_threshold: None,
+            endpoint_silence_ms: 700,
+            lookback_samples: 0,
+            turn_silence_ms: 0,
+            time_scale: 1.0,
+            half_window_refinement: false,
+            suppress_clipped_onsets: false,
+            initial_skip_ms: 0,
+            inclusive_threshold: true,
+            continuous_file: false,
+            probability_history: 0,
+            keep_long_segments: false,
+            bidirectional: false,
+        }
+    }
+}
+
+/// Cloneable detection state
+/// 
+/// Holds everything needed to resume detection at a given point of a stream:
+/// the segment being tracked, the timeline position and, in snapshots taken
+/// with [`VADIterator::state`], the model context. The ONNX session itself is
+/// not cloneable, so the state is kept separate from the model and can be
+/// snapshotted and restored against the same model to fork detection.
+#[derive(Debug, Clone, Default)]
+pub struct VadState {
+    speech_start: Option<f32>,
+    speech_end: Option<f32>,
+    speech_start_sample: usize,
+    speech_end_sample: usize,
+    last_prob: f32,
+    current_sample: usize,
+    last_voiced_sample: usize,
+    prob_sum: f32,
+    prob_count: u32,
+    pending_prob_sum: f32,
+    pending_prob_count: u32,
+    prob_peak: f32,
+    appended_tail: Vec<f32>,
+    onset_reported: bool,
+    utterance_active: bool,
+    utterance_last_voiced: usize,
+    previous_tail: Vec<f32>,
+    discontinuities: usize,
+    stats: VadStats,
+    history: Vec<f32>,
+    model_context: Option<Array2<f32>>,
+}
+
+impl VadState {
+    /// Number of samples processed since the state was created
+    pub fn processed_samples(&self) -> usize {
+        self.current_sample
+    }
+
+    /// Whether a speech segment is currently in progress
+    pub fn in_speech(&self) -> bool {
+        self.speech_start.is_some()
+    }
+
+    /// Cumulative statistics since the state was created
+    pub fn stats(&self) -> VadStats {
+        self.stats
+    }
+
+    /// Re-express the sample positions of the timeline at a new sampling rate
+    /// 
+    /// Segment times are kept in seconds and stay valid; buffered audio
+    /// belongs to the old rate and is dropped.
+    fn change_rate(&mut self, from_rate: u32, to_rate: u32) {
+        let convert = |index| crate::utils::convert_sample_index(index, from_rate, to_rate);
+        self.speech_start_sample = convert(self.speech_start_sample);
+        self.speech_end_sample = convert(self.speech_end_sample);
+        self.current_sample = convert(self.current_sample);
+        self.last_voiced_sample = convert(self.last_voiced_sample);
+        self.utterance_last_voiced = convert(self.utterance_last_voiced);
+        self.appended_tail.clear();
+        self.previous_tail.clear();
+        self.model_context = None;
+    }
+
+    /// Append a probability to the bounded history
+    /// 
+    /// The buffer grows to twice the capacity before the oldest half is
+    /// dropped, keeping appends amortized constant time while the last
+    /// `capacity` values stay contiguous.
+    fn record_history(&mut self, capacity: usize, prob: f32) {
+        if capacity == 0 {
+            return;
+        }
+        if self.history.len() >= 2 * capacity {
+            self.history.drain(..self.history.len() - capacity + 1);
+        }
+        self.history.push(prob);
+    }
+
+    /// The most recent probabilities in the history, oldest first
+    fn recent_probabilities(&self, capacity: usize) -> &[f32] {
+        &self.history[self.history.len().saturating_sub(capacity)..]
+    }
+
+    /// Forget the segment currently being tracked
+    fn clear_segment(&mut self) {
+        self.speech_start = None;
+        self.speech_end = None;
+        self.last_voiced_sample = 0;
+        self.prob_sum = 0.0;
+        self.prob_count = 0;
+        self.pending_prob_sum = 0.0;
+        self.pending_prob_count = 0;
+        self.prob_peak = 0.0;
+    }
+
+    /// Advance the segment state machine by one window
+    /// 
+    /// Times are derived from the running sample position, so segments are
+    /// reported relative to the start of the stream.
+    fn advance(&mut self, config: &VadConfig, prob: f32, chunk_len: usize) -> Option<SpeechTimestamps> {
+        let mut result = None;
+        let time_per_sample = config.time_scale / config.sampling_rate as f32;
+        let pad = config.speech_pad_ms as f32 / 1000.0 * config.time_scale;
+        let pad_samples = crate::utils::ms_to_samples(config.speech_pad_ms, config.sampling_rate);
+        let chunk_start_sample = self.current_sample;
+        let chunk_start = chunk_start_sample as f32 * time_per_sample;
+        // Windows starting in the skipped lead-in never count as speech
+        let skip_samples =
+            crate::utils::ms_to_samples(config.initial_skip_ms, config.sampling_rate);
+        let prob = if chunk_start_sample < skip_samples { 0.0 } else { prob };
+        self.record_history(config.probability_history, prob);
+        self.current_sample += chunk_len;
+        let current_time = self.current_sample as f32 * time_per_sample;
+
+        let voiced = config.is_speech(prob);
+        if voiced {
+            self.last_voiced_sample = self.current_sample;
+        }
+        // Hold the speech decision for a while after the probability drops
+        let hangover_samples = crate::utils::ms_to_samples(config.hangover_ms, config.sampling_rate);
+        let held = !voiced
+            && self.speech_start.is_some()
+            && chunk_start_sample < self.last_voiced_sample + hangover_samples;
+        // A pause reaching the turn silence splits the segment unconditionally
+        let turn = !voiced
+            && self.speech_start.is_some()
+            && config.is_turn(self.current_sample - self.last_voiced_sample);
+
+        if let Some(start) = self.speech_start.filter(|_| turn) {
+            let end = self.last_voiced_sample as f32 * time_per_sample + pad;
+            let confidence = self.prob_sum / self.prob_count.max(1) as f32;
+            result = Some(SpeechTimestamps {
+                start,
+                end,
+                confidence,
+                peak_confidence: self.prob_peak,
+                split_reason: SplitReason::Silence,
+                start_sample: self.speech_start_sample,
+                end_sample: self.last_voiced_sample + pad_samples,
+            });
+            self.clear_segment();
+        } else if voiced || held {
+            if self.speech_start.is_none() {
+                self.speech_start = Some(chunk_start);
+                self.speech_start_sample = chunk_start_sample;
+            }
+            self.speech_end = Some(current_time);
+            self.speech_end_sample = self.current_sample;
+            // Dips inside the segment count towards its confidence once speech resumes
+            self.prob_sum += self.pending_prob_sum + prob;
+            self.prob_count += self.pending_prob_count + 1;
+            self.pending_prob_sum = 0.0;
+            self.pending_prob_count = 0;
+            self.prob_peak = self.prob_peak.max(prob);
+        } else if let (Some(start), Some(speech_end)) = (self.speech_start, self.speech_end) {
+            self.pending_prob_sum += prob;
+            self.pending_prob_count += 1;
+
+            let silence_duration = (current_time - speech_end) / config.time_scale;
+            let silence_duration_ms = (silence_duration * 1000.0) as u32;
+
+            if silence_duration_ms >= config.min_silence_duration_ms {
+                let end = speech_end + pad;
+                let confidence = self.prob_sum / self.prob_count.max(1) as f32;
+                result = Some(SpeechTimestamps {
+                    start,
+                    end,
+                    confidence,
+                    peak_confidence: self.prob_peak,
+                    split_reason: SplitReason::Silence,
+                    start_sample: self.speech_start_sample,
+                    end_sample: self.speech_end_sample + pad_samples,
+                });
+                self.clear_segment();
+            }
+        }
+
+        let chunk_duration = chunk_len as f32 * time_per_sample;
+        if (voiced || held) && !turn {
+            self.stats.speech_duration_s += chunk_duration;
+        } else {
+            self.stats.silence_duration_s += chunk_duration;
+        }
+        if result.is_some() {
+            self.stats.segment_count += 1;
+        }
+
+        self.last_prob = prob;
+        result
+    }
+
+    /// Close the segment in progress at the current end of the stream
+    /// 
+    /// The padded end is clamped to the end of the stream.
+    fn finish(&mut self, config: &VadConfig) -> Option<SpeechTimestamps> {
+        let (start, speech_end) = (self.speech_start?, self.speech_end?);
+        let time_per_sample = config.time_scale / config.sampling_rate as f32;
+        let stream_end = self.current_sample as f32 * time_per_sample;
+        let pad = config.speech_pad_ms as f32 / 1000.0 * config.time_scale;
+        let pad_samples = crate::utils::ms_to_samples(config.speech_pad_ms, config.sampling_rate);
+        let result = SpeechTimestamps {
+            start,
+            end: (speech_end + pad).min(stream_end),
+            confidence: self.prob_sum / self.prob_count.max(1) as f32,
+            peak_confidence: self.prob_peak,
+            split_reason: SplitReason::Silence,
+            start_sample: self.speech_start_sample,
+            end_sample: (self.speech_end_sample + pad_samples).min(self.current_sample),
+        };
+        self.clear_segment();
+        self.stats.segment_count += 1;
+        Some(result)
+    }
+
+    /// Update the utterance endpoint for the window last passed to `advance`
+    fn endpoint(&mut self, config: &VadConfig) -> EndpointState {
+        if config.is_speech(self.last_prob) {
+            self.utterance_active = true;
+            self.utterance_last_voiced = self.current_sample;
+            return EndpointState::Speaking;
+        }
+        if !self.utterance_active {
+            return EndpointState::Listening;
+        }
+
+        let silence = self.current_sample - self.utterance_last_voiced;
+        if silence >= crate::utils::ms_to_samples(config.endpoint_silence_ms, config.sampling_rate) {
+            self.utterance_active = false;
+            EndpointState::Endpointed
+        } else {
+            EndpointState::Speaking
+        }
+    }
 }
 
 /// Iterator for processing audio in chunks
@@ -28,33 +886,32 @@ pub struct SpeechTimestamps {
 /// 
 /// # Example
 /// 
-/// ```rust
-/// use silero_vad::{SileroVAD, VADIterator};
+/// ```rust,no_run
+/// use silero_vad_rs::{SileroVAD, VADIterator};
 /// use ndarray::Array1;
+/// use std::path::Path;
 /// 
-/// let model = SileroVAD::new("path/to/model.onnx")?;
+/// # fn main() -> silero_vad_rs::Result<()> {
+/// let model = SileroVAD::new(Path::new("path/to/model.onnx"))?;
 /// let mut vad = VADIterator::new(
 ///     model,
 ///     0.5,  // threshold
 ///     16000, // sampling rate
 ///     100,   // min silence duration (ms)
 ///     30,    // speech pad (ms)
-/// );
+/// )?;
 /// 
 /// let audio_chunk = Array1::zeros(512);
 /// if let Some(ts) = vad.process_chunk(&audio_chunk.view())? {
 ///     println!("Speech detected from {:.2}s to {:.2}s", ts.start, ts.end);
 /// }
+/// # Ok(())
+/// # }
 /// ```
 pub struct VADIterator {
     model: SileroVAD,
-    threshold: f32,
-    sampling_rate: u32,
-    min_silence_duration_ms: u32,
-    speech_pad_ms: u32,
-    speech_start: Option<f32>,
-    speech_end: Option<f32>,
-    last_prob: f32,
+    config: VadConfig,
+    state: VadState,
 }
 
 impl VADIterator {
@@ -67,180 +924,2387 @@ impl VADIterator {
     /// * `sampling_rate` - Audio sampling rate (must be 16kHz)
     /// * `min_silence_duration_ms` - Minimum silence duration to end speech segment
     /// * `speech_pad_ms` - Padding to add to speech segments
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the sampling rate is not supported by the model
     pub fn new(
         model: SileroVAD,
         threshold: f32,
         sampling_rate: u32,
         min_silence_duration_ms: u32,
         speech_pad_ms: u32,
-    ) -> Self {
-        Self {
+    ) -> Result<Self> {
+        Self::from_config(
             model,
-            threshold,
-            sampling_rate,
-            min_silence_duration_ms,
-            speech_pad_ms,
-            speech_start: None,
-            speech_end: None,
-            last_prob: 0.0,
-        }
-    }
-
-    /// Reset the iterator state
-    /// 
-    /// This should be called when processing a new audio stream or when
-    /// you want to clear the internal state.
-    pub fn reset(&mut self) {
-        self.speech_start = None;
-        self.speech_end = None;
-        self.last_prob = 0.0;
-        self.model.reset_states(1);
+            VadConfig {
+                threshold,
+                sampling_rate,
+                min_silence_duration_ms,
+                speech_pad_ms,
+                ..Default::default()
+            },
+        )
     }
 
-    /// Process a single audio chunk and return speech timestamps if detected
+    /// Create a new VAD iterator from a configuration
     /// 
     /// # Arguments
     /// 
-    /// * `x` - Audio chunk to process (must be 512 samples for 16kHz)
-    /// 
-    /// # Returns
-    /// 
-    /// `Ok(Some(timestamps))` if speech is detected, `Ok(None)` otherwise
+    /// * `model` - The Silero VAD model to use
+    /// * `config` - Detector configuration, e.g. one previously saved from [`VADIterator::config`]
     /// 
     /// # Errors
     /// 
     /// Returns an error if:
-    /// * The input chunk size is invalid
-    /// * Model inference fails
-    pub fn process_chunk(&mut self, x: &ArrayView1<f32>) -> Result<Option<SpeechTimestamps>> {
-        let prob = self.model.process_chunk(x, self.sampling_rate)?;
-        let prob = prob[0];
-
-        let mut result = None;
-        let time_per_sample = 1.0 / self.sampling_rate as f32;
-        let current_time = (x.len() as f32) * time_per_sample;
-
-        if prob >= self.threshold {
-            if self.speech_start.is_none() {
-                self.speech_start = Some(current_time);
-            }
-            self.speech_end = Some(current_time);
-        } else if self.speech_start.is_some() {
-            let silence_duration = current_time - self.speech_end.unwrap();
-            let silence_duration_ms = (silence_duration * 1000.0) as u32;
-
-            if silence_duration_ms >= self.min_silence_duration_ms {
-                let start = self.speech_start.unwrap();
-                let end = self.speech_end.unwrap() + (self.speech_pad_ms as f32 / 1000.0);
-                result = Some(SpeechTimestamps { start, end });
-                self.reset();
-            }
-        }
-
-        self.last_prob = prob;
-        Ok(result)
+    /// * The sampling rate is not supported by the model
+    /// * [`VadConfig::time_scale`] is not a positive, finite factor
+    pub fn from_config(model: SileroVAD, config: VadConfig) -> Result<Self> {
+        crate::model::check_sampling_rate(config.sampling_rate)?;
+        check_time_scale(config.time_scale)?;
+        Ok(Self {
+            model,
+            config,
+            state: VadState::default(),
+        })
     }
 
-    /// Get speech timestamps for an entire audio file
+    /// Hold the speech decision after the probability drops below the threshold
+    /// 
+    /// Unlike `min_silence_duration_ms`, which decides when a segment closes,
+    /// and `speech_pad_ms`, which extends the reported boundary, the hangover
+    /// keeps windows following speech classified as speech. This prevents
+    /// mid-word dropouts on short unvoiced sounds.
     /// 
     /// # Arguments
     /// 
-    /// * `audio` - Complete audio file to process
-    /// * `min_speech_duration_ms` - Minimum duration of speech segments
-    /// * `max_speech_duration_s` - Maximum duration of speech segments
-    /// * `min_silence_duration_ms` - Minimum silence duration between segments
-    /// * `speech_pad_ms` - Padding to add to speech segments
+    /// * `hangover_ms` - Hangover duration in milliseconds (0 disables it)
+    pub fn with_hangover_ms(mut self, hangover_ms: u32) -> Self {
+        self.config.hangover_ms = hangover_ms;
+        self
+    }
+
+    /// Fill brief dropouts inside speech before segmentation
     /// 
-    /// # Returns
+    /// Runs of up to `max_gap_windows` sub-threshold windows surrounded by
+    /// speech are treated as speech (see [`VadConfig::fill_gaps`]). This acts
+    /// at the window level, before segments are built, and therefore only
+    /// applies to [`VADIterator::get_speech_timestamps`], which sees the whole
+    /// probability sequence at once.
     /// 
-    /// Vector of speech timestamps for all detected segments
+    /// # Arguments
+    /// 
+    /// * `max_gap_windows` - Longest run of windows to fill (0 disables gap filling)
+    pub fn with_gap_fill(mut self, max_gap_windows: usize) -> Self {
+        self.config.gap_fill_windows = max_gap_windows;
+        self
+    }
+
+    /// Soft-limit loud peaks before inference
+    /// 
+    /// Samples whose magnitude exceeds `threshold` are compressed smoothly
+    /// towards full scale (see [`crate::utils::soft_limit`]), so transients
+    /// such as door slams trigger the model less without hard clipping.
+    /// Unlike gain or normalization, samples below the threshold are left
+    /// untouched. Segmentation and returned audio are not affected.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `threshold` - Magnitude where limiting starts, in `(0.0, 1.0)`
+    pub fn with_limiter(mut self, threshold: f32) -> Self {
+        self.config.limiter_threshold = Some(threshold);
+        self
+    }
+
+    /// Set the pause that always splits a segment, e.g. at speaker turns
+    /// 
+    /// # Arguments
+    /// 
+    /// * `silence_ms` - Pause length that forces a segment boundary (0 disables it)
+    pub fn with_turn_silence_ms(mut self, silence_ms: u32) -> Self {
+        self.config.turn_silence_ms = silence_ms;
+        self
+    }
+
+    /// Ignore a known-bad lead-in at the start of the stream, see [`VadConfig::initial_skip_ms`]
+    /// 
+    /// # Arguments
+    /// 
+    /// * `skip_ms` - Lead-in never treated as speech
+    pub fn with_initial_skip_ms(mut self, skip_ms: u32) -> Self {
+        self.config.initial_skip_ms = skip_ms;
+        self
+    }
+
+    /// Keep the last window probabilities for debugging, see [`VADIterator::recent_probabilities`]
+    /// 
+    /// # Arguments
+    /// 
+    /// * `windows` - Number of probabilities to keep (0 disables the history)
+    pub fn with_probability_history(mut self, windows: usize) -> Self {
+        self.config.probability_history = windows;
+        self
+    }
+
+    /// Keep detection state across offline calls, see [`VadConfig::continuous_file`]
+    /// 
+    /// # Arguments
+    /// 
+    /// * `enabled` - Whether consecutive calls continue the same stream
+    pub fn with_continuous_file(mut self, enabled: bool) -> Self {
+        self.config.continuous_file = enabled;
+        self
+    }
+
+    /// Set the detection threshold used at a specific sampling rate
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sampling_rate` - Sampling rate the threshold applies to
+    /// * `threshold` - Speech detection threshold (0.0 to 1.0)
+    pub fn with_rate_threshold(mut self, sampling_rate: u32, threshold: f32) -> Self {
+        self.config.rate_thresholds.insert(sampling_rate, threshold);
+        self
+    }
+
+    /// Switch to the short-command preset, see [`VadConfig::command_mode`]
+    /// 
+    /// The sampling rate and class index are kept; every other setting is
+    /// replaced by the preset.
+    pub fn with_command_mode(mut self) -> Self {
+        self.config = VadConfig {
+            class_index: self.config.class_index,
+            ..VadConfig::command_mode(self.config.sampling_rate)
+        };
+        self
+    }
+
+    /// Set the factor applied to every reported time, see [`VadConfig::time_scale`]
+    /// 
+    /// # Arguments
+    /// 
+    /// * `time_scale` - Ratio of the caller's timeline to the detection timeline
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if `time_scale` is not a positive, finite factor
+    pub fn with_time_scale(mut self, time_scale: f32) -> Result<Self> {
+        check_time_scale(time_scale)?;
+        self.config.time_scale = time_scale;
+        Ok(self)
+    }
+
+    /// Set the trailing silence that ends an utterance in [`VADIterator::poll_endpoint`]
+    /// 
+    /// # Arguments
+    /// 
+    /// * `silence_ms` - Silence after speech before the endpoint fires
+    pub fn with_endpoint_silence_ms(mut self, silence_ms: u32) -> Self {
+        self.config.endpoint_silence_ms = silence_ms;
+        self
+    }
+
+    /// Get the detector configuration
+    pub fn config(&self) -> &VadConfig {
+        &self.config
+    }
+
+    /// Duration of one model window in milliseconds at the configured rate
+    pub fn window_duration_ms(&self) -> f32 {
+        self.window_size() as f32 * 1000.0 / self.config.sampling_rate as f32
+    }
+
+    /// Number of samples processed since the last reset
+    pub fn processed_samples(&self) -> usize {
+        self.state.current_sample
+    }
+
+    /// Take a snapshot of the detection state, including the model context
+    /// 
+    /// The snapshot can later be passed to [`VADIterator::restore_state`] to
+    /// resume detection from this exact point, e.g. to run the same stream
+    /// forward with two different configurations.
+    pub fn state(&self) -> VadState {
+        let mut state = self.state.clone();
+        state.model_context = Some(self.model.context().to_owned());
+        state
+    }
+
+    /// Restore a detection state previously taken with [`VADIterator::state`]
+    /// 
+    /// A state without a model context (e.g. `VadState::default()`) clears
+    /// the model context.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the stored model context has an invalid shape
+    pub fn restore_state(&mut self, mut state: VadState) -> Result<()> {
+        match state.model_context.take() {
+            Some(context) => self.model.set_context(context)?,
+            None => self.reset_model(),
+        }
+        self.state = state;
+        Ok(())
+    }
+
+    /// Reset the iterator state
+    /// 
+    /// This should be called when processing a new audio stream or when
+    /// you want to clear the internal state. It is equivalent to calling
+    /// [`VADIterator::reset_segments`] and [`VADIterator::reset_model`], and
+    /// additionally restarts the timeline at zero.
+    pub fn reset(&mut self) {
+        self.reset_segments();
+        self.reset_model();
+        self.state.last_prob = 0.0;
+        self.state.current_sample = 0;
+        self.state.appended_tail.clear();
+        self.state.onset_reported = false;
+        self.state.utterance_active = false;
+        self.state.previous_tail.clear();
+        self.state.discontinuities = 0;
+        self.state.stats = VadStats::default();
+        self.state.history.clear();
+    }
+
+    /// Forget the segment currently being tracked
+    /// 
+    /// The model context and the timeline are kept, so detection continues
+    /// seamlessly on the same audio, but an in-progress segment is dropped
+    /// and the next speech window opens a new one.
+    pub fn reset_segments(&mut self) {
+        self.state.clear_segment();
+    }
+
+    /// Clear the model context
+    /// 
+    /// Useful when the acoustic scene changes (e.g. a new input device) while
+    /// an in-progress segment and the timeline should be kept.
+    pub fn reset_model(&mut self) {
+        self.model.reset_states(1);
+    }
+
+    /// Worst-case delay between the end of speech and the emission of its segment
+    /// 
+    /// Computed from the current configuration, see
+    /// [`VadConfig::emission_latency_ms`].
+    pub fn emission_latency_ms(&self) -> u32 {
+        self.config.emission_latency_ms()
+    }
+
+    /// Get the statistics of the stream so far
+    /// 
+    /// Updated with every processed window, so a live display can show e.g.
+    /// "3 utterances, 12.4s of speech so far". Cleared by [`VADIterator::reset`].
+    pub fn running_stats(&self) -> VadStats {
+        self.state.stats
+    }
+
+    /// Get the most recent window probabilities, oldest first
+    /// 
+    /// Holds up to [`VadConfig::probability_history`] values and is empty when
+    /// the history is disabled. Logging it when an unexpected onset or offset
+    /// is detected shows what led up to the decision. Cleared by
+    /// [`VADIterator::reset`].
+    pub fn recent_probabilities(&self) -> &[f32] {
+        self.state.recent_probabilities(self.config.probability_history)
+    }
+
+    /// Get the underlying model
+    pub fn model(&self) -> &SileroVAD {
+        &self.model
+    }
+
+    /// Warm up the model with audio preceding the point where detection starts
+    /// 
+    /// The audio is run through the model in full windows so that its carried
+    /// context reflects the preceding signal, but no segments are emitted and
+    /// the reported timeline does not advance. A trailing partial window is ignored.
+    /// 
+    /// Without priming, the first window after starting mid-stream is scored
+    /// with an empty context, which can delay or miss an onset that is already
+    /// under way. A few hundred milliseconds of preceding audio is usually
+    /// enough; priming with more than a second brings no measurable benefit.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `context_audio` - Audio immediately preceding the stream to analyze
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if model inference fails
+    pub fn prime(&mut self, context_audio: &ArrayView1<f32>) -> Result<()> {
+        let chunk_size = self.window_size();
+        for window in context_audio.exact_chunks(chunk_size) {
+            self.chunk_classes(&window, self.config.sampling_rate)?;
+        }
+        Ok(())
+    }
+
+    /// Check up front that a WAV file matches the configured sampling rate
+    /// 
+    /// A file at a different rate than the detector silently produces poor
+    /// results, so call this before reading the file to fail early with a
+    /// clear message instead.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Path to the WAV file that will be analyzed
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the file cannot be read or its sampling rate
+    /// differs from the configured one
+    pub fn check_wav_rate<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        let file_rate = crate::utils::wav_sampling_rate(&path)?;
+        if file_rate != self.config.sampling_rate {
+            return Err(Error::InvalidInput(format!(
+                "{} is sampled at {} Hz but the detector is configured for {} Hz; \
+                 resample it on read (ReadOptions::resample) or configure the detector \
+                 with VadConfig::for_wav",
+                path.as_ref().display(),
+                file_rate,
+                self.config.sampling_rate
+            )));
+        }
+        Ok(())
+    }
+
+    /// Number of samples the model expects per window at the configured rate
+    fn window_size(&self) -> usize {
+        window_size_for(self.config.sampling_rate)
+    }
+
+    /// Reject audio too short for offline detection to be reliable
+    fn check_audio_length(&self, audio_len: usize) -> Result<()> {
+        let windows = audio_len / self.window_size();
+        if windows < self.config.min_audio_windows {
+            return Err(Error::InvalidInput(format!(
+                "Audio holds {} window(s) but at least {} are required; the detector \
+                 needs more context to be reliable. Pass longer audio or lower \
+                 VadConfig::min_audio_windows (0 disables this check)",
+                windows, self.config.min_audio_windows
+            )));
+        }
+        Ok(())
+    }
+
+    /// Advance the segment state machine by one window
+    /// 
+    /// The model context is cleared whenever a segment is emitted, unless
+    /// [`VadConfig::continuous_file`] is set.
+    fn advance(&mut self, prob: f32, chunk_len: usize) -> Option<SpeechTimestamps> {
+        let result = self.state.advance(&self.config, prob, chunk_len);
+        if let Some(ts) = &result {
+            events::segment_detected(ts);
+            if !self.config.continuous_file {
+                self.reset_model();
+            }
+        }
+        result
+    }
+
+    /// Score one streaming chunk and advance the segment state machine
+    /// 
+    /// Applies the configured lookback to refine the onset of a segment
+    /// opened by this chunk.
+    fn step(&mut self, x: &ArrayView1<f32>) -> Result<(f32, Option<SpeechTimestamps>)> {
+        let sr = self.config.sampling_rate;
+        let lookback = self.config.lookback_samples;
+        let uses_lookback = lookback > 0 && lookback < x.len();
+        let was_in_speech = self.state.in_speech();
+        let context = uses_lookback.then(|| self.model.context().to_owned());
+        let probs = self.chunk_classes(x, sr)?;
+        let prob = self.class_probability(&probs)?;
+
+        let mut onset_shift = 0;
+        if let Some(context) = context.filter(|_| {
+            !was_in_speech
+                && self.config.is_speech(prob)
+                && self.state.previous_tail.len() == lookback
+        }) {
+            // Score the window straddling the boundary from the same context
+            let mut probe = self.state.previous_tail.clone();
+            probe.extend(x.iter().take(x.len() - lookback));
+            let after = self.model.context().to_owned();
+            self.model.set_context(context)?;
+            let probe_probs = self.chunk_classes(&ArrayView1::from(&probe[..]), sr)?;
+            self.model.set_context(after)?;
+            if self.config.is_speech(self.class_probability(&probe_probs)?) {
+                onset_shift = lookback;
+            }
+        }
+
+        let segment = self.advance(prob, x.len());
+        if onset_shift > 0 && !was_in_speech {
+            if let Some(start) = self.state.speech_start.as_mut() {
+                let shift = onset_shift as f32 * self.config.time_scale / sr as f32;
+                *start = (*start - shift).max(0.0);
+                self.state.speech_start_sample =
+                    self.state.speech_start_sample.saturating_sub(onset_shift);
+            }
+        }
+
+        if uses_lookback {
+            self.state.previous_tail = x.iter().skip(x.len() - lookback).copied().collect();
+        }
+        Ok((prob, segment))
+    }
+
+    /// Score a trailing partial window and advance the state by its real length
+    /// 
+    /// The window is zero-padded to the model's window size for inference
+    /// only; the timeline, durations and any emitted segment count just the
+    /// samples actually present.
+    fn process_partial_window(&mut self, x: &ArrayView1<f32>) -> Result<Option<SpeechTimestamps>> {
+        let mut window = Array1::zeros(self.window_size());
+        window.slice_mut(ndarray::s![..x.len()]).assign(x);
+        let probs = self.chunk_classes(&window.view(), self.config.sampling_rate)?;
+        let prob = self.class_probability(&probs)?;
+        Ok(self.advance(prob, x.len()))
+    }
+
+    /// Run one window through the model, applying the configured limiter
+    fn chunk_classes(&mut self, x: &ArrayView1<f32>, sr: u32) -> Result<Vec<f32>> {
+        match self.config.limiter_threshold {
+            Some(threshold) => {
+                let mut limited = x.to_owned();
+                crate::utils::soft_limit(&mut limited, threshold);
+                self.model.process_chunk_classes(&limited.view(), sr)
+            }
+            None => self.model.process_chunk_classes(x, sr),
+        }
+    }
+
+    /// Run a batch of windows through the model, applying the configured limiter
+    fn batch_classes(&mut self, x: &Array2<f32>) -> Result<Array2<f32>> {
+        let sr = self.config.sampling_rate;
+        match self.config.limiter_threshold {
+            Some(threshold) => {
+                let mut limited = x.clone();
+                for mut row in limited.rows_mut() {
+                    row.mapv_inplace(|v| crate::utils::soft_limit_sample(v, threshold));
+                }
+                self.model.process_batch_classes(&limited, sr)
+            }
+            None => self.model.process_batch_classes(x, sr),
+        }
+    }
+
+    /// Pick the configured class out of a model output row
+    fn class_probability(&self, probs: &[f32]) -> Result<f32> {
+        probs.get(self.config.class_index).copied().ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "Class index {} is out of range for a model with {} output(s)",
+                self.config.class_index,
+                probs.len()
+            ))
+        })
+    }
+
+    /// Process a single audio chunk and return speech timestamps if detected
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Audio chunk to process (must be 512 samples for 16kHz)
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(Some(timestamps))` if speech is detected, `Ok(None)` otherwise
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The input chunk size is invalid
+    /// * Model inference fails
+    pub fn process_chunk(&mut self, x: &ArrayView1<f32>) -> Result<Option<SpeechTimestamps>> {
+        let (_, segment) = self.step(x)?;
+        Ok(segment)
+    }
+
+    /// Finalize the segment in progress, e.g. at the end of a stream
+    /// 
+    /// Streaming detection only closes a segment after enough silence, so
+    /// speech running up to the end of the audio is otherwise never reported.
+    /// The padded end is clamped to the end of the processed audio.
+    /// 
+    /// # Returns
+    /// 
+    /// The segment in progress, or `None` if there is none
+    pub fn flush(&mut self) -> Option<SpeechTimestamps> {
+        let result = self.state.finish(&self.config);
+        if let Some(ts) = &result {
+            events::segment_detected(ts);
+        }
+        result
+    }
+
+    /// Lazily detect segments, yielding each one as soon as it is finalized
+    /// 
+    /// Inference runs window by window as the iterator is advanced, so a
+    /// caller can stream segments to a UI or a downstream stage while the
+    /// rest of the audio is still unprocessed. A trailing partial window is
+    /// zero-padded (the timeline only advances by its real length), and a
+    /// segment still open at the end of the audio is finalized with
+    /// [`VADIterator::flush`]. Segments are not filtered by duration.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Audio to process
+    /// * `sr` - Sampling rate of the audio, which must match the configured rate
+    /// 
+    /// # Returns
+    /// 
+    /// An iterator of segments in order. After an error, which is yielded as
+    /// an `Err` item (e.g. for a mismatched `sr` or failed inference), the
+    /// iterator ends.
+    pub fn segments_iter<'a>(
+        &'a mut self,
+        audio: &'a ArrayView1<'_, f32>,
+        sr: u32,
+    ) -> impl Iterator<Item = Result<SpeechTimestamps>> + 'a {
+        let audio = audio.view();
+        let window_size = window_size_for(sr);
+        let mut position = 0;
+        let mut finished = false;
+
+        std::iter::from_fn(move || {
+            if finished {
+                return None;
+            }
+            if sr != self.config.sampling_rate {
+                finished = true;
+                return Some(Err(Error::InvalidInput(format!(
+                    "Audio at {} Hz passed to a detector configured for {} Hz",
+                    sr, self.config.sampling_rate
+                ))));
+            }
+
+            while position < audio.len() {
+                let end = (position + window_size).min(audio.len());
+                let padding = window_size - (end - position);
+                let window = audio.slice(ndarray::s![position..end]);
+                let result = if padding == 0 {
+                    self.process_chunk(&window)
+                } else {
+                    self.process_partial_window(&window)
+                };
+                position = end;
+
+                match result {
+                    Ok(Some(ts)) => return Some(Ok(ts)),
+                    Ok(None) => {}
+                    Err(e) => {
+                        finished = true;
+                        return Some(Err(e));
+                    }
+                }
+            }
+
+            finished = true;
+            self.flush().map(Ok)
+        })
+    }
+
+    /// Process a chunk that the caller expects to start at a given sample
+    /// 
+    /// A continuity check for integrations that buffer audio themselves:
+    /// `sample_position` is where the caller believes the chunk starts in the
+    /// stream, i.e. the running total of samples it has passed in so far.
+    /// If it differs from [`VADIterator::processed_samples`], chunks were
+    /// skipped, repeated or reordered upstream, which silently degrades
+    /// accuracy because the model context no longer matches the audio. The
+    /// mismatch is logged as a warning and counted in
+    /// [`VADIterator::discontinuities`]; the chunk is processed regardless.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Audio chunk to process (must be 512 samples for 16kHz)
+    /// * `sample_position` - Stream position of the first sample of `x`
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(Some(timestamps))` if speech is detected, `Ok(None)` otherwise
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The input chunk size is invalid
+    /// * Model inference fails
+    pub fn process_chunk_at(
+        &mut self,
+        x: &ArrayView1<f32>,
+        sample_position: usize,
+    ) -> Result<Option<SpeechTimestamps>> {
+        let expected = self.state.current_sample;
+        if sample_position != expected {
+            events::stream_discontinuity(expected, sample_position);
+            self.state.discontinuities += 1;
+        }
+        self.process_chunk(x)
+    }
+
+    /// Process a chunk from a source whose sampling rate may change mid-stream
+    /// 
+    /// Live sources such as reconnecting network streams can renegotiate
+    /// their rate. When `sr` differs from the configured rate, the detector
+    /// switches to it with [`VADIterator::switch_sampling_rate`] before the
+    /// chunk is processed, so chunk sizing and timestamps follow the new rate.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Audio chunk to process, one window at `sr`
+    /// * `sr` - Sampling rate of the chunk
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(Some(timestamps))` if speech is detected, `Ok(None)` otherwise
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The sampling rate is not supported by the model; the detector is
+    ///   left unchanged
+    /// * The input chunk size is invalid
+    /// * Model inference fails
+    pub fn process_chunk_with_rate(
+        &mut self,
+        x: &ArrayView1<f32>,
+        sr: u32,
+    ) -> Result<Option<SpeechTimestamps>> {
+        self.switch_sampling_rate(sr)?;
+        self.process_chunk(x)
+    }
+
+    /// Continue the stream at a different sampling rate
+    /// 
+    /// The model context is cleared, since it belongs to audio at the old
+    /// rate, while the timeline continues: positions and the sample-based
+    /// settings (`min_speech_samples`, `max_speech_samples`,
+    /// `lookback_samples`) are converted to the new rate, so reported times
+    /// stay continuous and an in-progress segment is kept. Switching to the
+    /// current rate does nothing.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sr` - New sampling rate of the stream
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the sampling rate is not supported by the model;
+    /// the detector is left unchanged
+    pub fn switch_sampling_rate(&mut self, sr: u32) -> Result<()> {
+        let old = self.config.sampling_rate;
+        if sr == old {
+            return Ok(());
+        }
+        crate::model::check_sampling_rate(sr)?;
+
+        let convert = |samples| crate::utils::convert_sample_index(samples, old, sr);
+        self.state.change_rate(old, sr);
+        self.config.min_speech_samples = self.config.min_speech_samples.map(convert);
+        self.config.max_speech_samples = self.config.max_speech_samples.map(convert);
+        self.config.lookback_samples = convert(self.config.lookback_samples);
+        self.config.sampling_rate = sr;
+        self.reset_model();
+        Ok(())
+    }
+
+    /// Number of discontinuities detected by [`VADIterator::process_chunk_at`]
+    /// since the last reset
+    pub fn discontinuities(&self) -> usize {
+        self.state.discontinuities
+    }
+
+    /// Process a single audio chunk and report speech boundary events
+    /// 
+    /// Unlike [`VADIterator::process_chunk`], which only reports completed
+    /// segments, this also reports the moment a segment opens and, on every
+    /// following chunk, its current extent, so a UI can draw a live, growing
+    /// highlight. The closing [`VadEvent::SpeechEnd`] carries the padded
+    /// boundary. Each event can be streamed with [`VadEvent::write_json_line`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Audio chunk to process (must be 512 samples for 16kHz)
+    /// 
+    /// # Returns
+    /// 
+    /// Events triggered by this chunk, in order
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The input chunk size is invalid
+    /// * Model inference fails
+    pub fn process_chunk_events(&mut self, x: &ArrayView1<f32>) -> Result<Vec<VadEvent>> {
+        let was_in_speech = self.state.in_speech();
+        let (prob, segment) = self.step(x)?;
+        Ok(chunk_events(&self.state, was_in_speech, prob, segment))
+    }
+
+    /// Process a streaming chunk and report when speech first starts
+    /// 
+    /// Returns the onset time the first time a speech segment opens, and
+    /// `None` for every other chunk, including later onsets. This is the
+    /// "wait for the user to start talking" trigger; call
+    /// [`VADIterator::reset`] to arm it again.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Audio chunk to process (must be 512 samples for 16kHz)
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(Some(time_s))` for the first onset, `Ok(None)` otherwise
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The input chunk size is invalid
+    /// * Model inference fails
+    pub fn poll_first_speech(&mut self, x: &ArrayView1<f32>) -> Result<Option<f32>> {
+        let events = self.process_chunk_events(x)?;
+        if self.state.onset_reported {
+            return Ok(None);
+        }
+
+        let onset = events.iter().find_map(|event| match event {
+            VadEvent::SpeechStart { timestamp, .. } => Some(*timestamp),
+            _ => None,
+        });
+        self.state.onset_reported = onset.is_some();
+        Ok(onset)
+    }
+
+    /// Process a streaming chunk and track whether the user is talking
+    /// 
+    /// Reports [`EndpointState::Endpointed`] exactly once per utterance, as
+    /// soon as [`VadConfig::endpoint_silence_ms`] of trailing silence has
+    /// elapsed after speech. Unlike segment emission, which is tuned by
+    /// `min_silence_duration_ms` to split speech finely, the endpoint
+    /// silence is usually longer so natural pauses do not end the turn.
+    /// Segment tracking continues underneath, so this can be mixed with
+    /// [`VADIterator::state`] snapshots and the other streaming methods.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Audio chunk to process (must be 512 samples for 16kHz)
+    /// 
+    /// # Returns
+    /// 
+    /// The endpoint state after the chunk, together with the segment the
+    /// chunk finalized, if any, as [`VADIterator::process_chunk`] returns it
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The input chunk size is invalid
+    /// * Model inference fails
+    pub fn poll_endpoint(
+        &mut self,
+        x: &ArrayView1<f32>,
+    ) -> Result<(EndpointState, Option<SpeechTimestamps>)> {
+        let (_, segment) = self.step(x)?;
+        Ok((self.state.endpoint(&self.config), segment))
+    }
+
+    /// Continue detection on audio appended to a growing buffer
+    /// 
+    /// Only the newly appended samples are passed in; the detector picks up
+    /// exactly where the previous call stopped. Samples that do not fill a
+    /// whole window are kept and processed together with the next append, so
+    /// the buffer can grow by arbitrary amounts. Returned segments carry
+    /// absolute timestamps from the start of the buffer.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `new_tail` - Samples appended since the previous call
+    /// 
+    /// # Returns
+    /// 
+    /// Segments completed by the appended audio
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if model inference fails. Windows not yet processed
+    /// are kept for the next call.
+    pub fn process_appended(&mut self, new_tail: &ArrayView1<f32>) -> Result<Vec<SpeechTimestamps>> {
+        let chunk_size = self.window_size();
+        let mut buffer = std::mem::take(&mut self.state.appended_tail);
+        buffer.extend(new_tail.iter().copied());
+
+        let mut segments = Vec::new();
+        let mut offset = 0;
+        while buffer.len() - offset >= chunk_size {
+            let window = ArrayView1::from(&buffer[offset..offset + chunk_size]);
+            match self.process_chunk(&window) {
+                Ok(Some(ts)) => segments.push(ts),
+                Ok(None) => {}
+                Err(e) => {
+                    buffer.drain(..offset);
+                    self.state.appended_tail = buffer;
+                    return Err(e);
+                }
+            }
+            offset += chunk_size;
+        }
+
+        buffer.drain(..offset);
+        self.state.appended_tail = buffer;
+        Ok(segments)
+    }
+
+    /// Detect speech on audio that is already split into windows
+    /// 
+    /// Each row is one window of consecutive, non-overlapping audio and is
+    /// run through the model in order, so pipelines that window audio
+    /// upstream need not flatten it first. Timestamps follow from the row
+    /// index: on a fresh iterator row `i` starts at `i * window_size` samples,
+    /// otherwise the rows continue the iterator's timeline.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `windows` - Audio windows with shape `[n_windows, window_size]`
+    /// 
+    /// # Returns
+    /// 
+    /// Segments completed within the windows
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The window size does not match the model (512 samples for 16kHz)
+    /// * Model inference fails
+    pub fn detect_windows(&mut self, windows: &Array2<f32>) -> Result<Vec<SpeechTimestamps>> {
+        let mut segments = Vec::new();
+        for window in windows.rows() {
+            if let Some(ts) = self.process_chunk(&window)? {
+                segments.push(ts);
+            }
+        }
+        Ok(segments)
+    }
+
+    /// Process interleaved multi-channel audio and detect speech on its mono mix
+    /// 
+    /// Frames are downmixed by averaging the channels, then buffered to whole
+    /// windows exactly like [`VADIterator::process_appended`], so capture
+    /// buffers of any length can be passed straight from the audio device.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `frame` - Interleaved samples, e.g. `[L, R, L, R, ...]` for stereo
+    /// * `channels` - Number of interleaved channels
+    /// 
+    /// # Returns
+    /// 
+    /// Segments completed by this buffer
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The channel count is zero
+    /// * The buffer length is not a whole number of frames
+    /// * Model inference fails
+    pub fn process_interleaved(&mut self, frame: &[f32], channels: u16) -> Result<Vec<SpeechTimestamps>> {
+        let channels = channels as usize;
+        if channels == 0 {
+            return Err(Error::InvalidInput("Channel count must be at least 1".into()));
+        }
+        if frame.len() % channels != 0 {
+            return Err(Error::InvalidInput(format!(
+                "Buffer of {} samples is not a whole number of {}-channel frames",
+                frame.len(),
+                channels
+            )));
+        }
+
+        let mono: Array1<f32> = frame
+            .chunks_exact(channels)
+            .map(|f| f.iter().sum::<f32>() / channels as f32)
+            .collect();
+        self.process_appended(&mono.view())
+    }
+
+    /// Stream audio through the detector until a callback asks to stop
+    /// 
+    /// The audio is fed window by window as with
+    /// [`VADIterator::process_chunk`] (a trailing partial window is ignored),
+    /// and every finalized segment is passed to `on_segment`. Returning
+    /// [`ControlFlow::Break`] stops processing right away, so the rest of a
+    /// long file is never run through the model, e.g. once the first speech
+    /// has been found.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Audio to process
+    /// * `sr` - Sampling rate of the audio, which must match the configured rate
+    /// * `on_segment` - Called with each finalized segment; decides whether to go on
+    /// 
+    /// # Returns
+    /// 
+    /// The segments finalized before stopping, including the one that stopped processing
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * `sr` differs from the configured sampling rate
+    /// * Model inference fails
+    pub fn process_until<F>(
+        &mut self,
+        audio: &ArrayView1<f32>,
+        sr: u32,
+        mut on_segment: F,
+    ) -> Result<Vec<SpeechTimestamps>>
+    where
+        F: FnMut(&SpeechTimestamps) -> ControlFlow<()>,
+    {
+        if sr != self.config.sampling_rate {
+            return Err(Error::InvalidInput(format!(
+                "Audio at {} Hz passed to a detector configured for {} Hz",
+                sr, self.config.sampling_rate
+            )));
+        }
+
+        let mut segments = Vec::new();
+        for window in audio.exact_chunks(self.window_size()) {
+            if let Some(ts) = self.process_chunk(&window)? {
+                let flow = on_segment(&ts);
+                segments.push(ts);
+                if flow.is_break() {
+                    break;
+                }
+            }
+        }
+        Ok(segments)
+    }
+
+    /// Get speech timestamps for an entire audio file
+    /// 
+    /// With [`VadConfig::continuous_file`] set, the audio is instead treated
+    /// as the next piece of a longer stream, see
+    /// [`VADIterator::process_appended`].
+    /// 
+    /// Audio shorter than one window (accepted unless
+    /// [`VadConfig::min_audio_windows`] is raised) is zero-padded to one window and
+    /// scored as a whole, so at most one segment, covering the real samples,
+    /// is returned.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Complete audio file to process
+    /// * `min_speech_duration_ms` - Minimum duration of speech segments
+    /// * `max_speech_duration_s` - Maximum duration of speech segments
+    /// * `min_silence_duration_ms` - Minimum silence duration between segments
+    /// * `speech_pad_ms` - Padding to add to speech segments
+    /// 
+    /// # Returns
+    /// 
+    /// Vector of speech timestamps for all detected segments
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The audio is empty
+    /// * The audio data is invalid
+    /// * The audio is shorter than [`VadConfig::min_audio_windows`] windows
+    /// * Model inference fails
+    pub fn get_speech_timestamps(
+        &mut self,
+        audio: &ArrayView1<f32>,
+        min_speech_duration_ms: u32,
+        max_speech_duration_s: f32,
+        _min_silence_duration_ms: u32,
+        _speech_pad_ms: u32,
+    ) -> Result<Vec<SpeechTimestamps>> {
+        if audio.is_empty() {
+            return Err(Error::InvalidInput(
+                "Audio buffer is empty; pass at least one sample".into(),
+            ));
+        }
+
+        if self.config.continuous_file {
+            let mut timestamps = self.process_appended(audio)?;
+            timestamps.retain_mut(|ts| {
+                keep_segment(ts, &self.config, min_speech_duration_ms, max_speech_duration_s)
+            });
+            return Ok(timestamps);
+        }
+
+        self.detect_offline(audio, min_speech_duration_ms, max_speech_duration_s)
+    }
+
+    /// Run offline detection and drop clipped onsets if configured to
+    fn detect_offline(
+        &mut self,
+        audio: &ArrayView1<f32>,
+        min_speech_duration_ms: u32,
+        max_speech_duration_s: f32,
+    ) -> Result<Vec<SpeechTimestamps>> {
+        let timestamps =
+            self.detect_timestamps(audio, min_speech_duration_ms, max_speech_duration_s)?;
+        if !self.config.suppress_clipped_onsets {
+            return Ok(timestamps);
+        }
+
+        let sr = self.config.sampling_rate;
+        Ok(crate::utils::suppress_clipped_onsets_by_sample(&timestamps, audio, sr))
+    }
+
+    /// Run offline detection, see [`VADIterator::get_speech_timestamps`]
+    fn detect_timestamps(
+        &mut self,
+        audio: &ArrayView1<f32>,
+        min_speech_duration_ms: u32,
+        max_speech_duration_s: f32,
+    ) -> Result<Vec<SpeechTimestamps>> {
+        self.check_audio_length(audio.len())?;
+        let mut timestamps = Vec::new();
+        let chunk_size = self.window_size();
+
+        if audio.len() < chunk_size {
+            // Score the clip as one zero-padded window; the timeline only
+            // advances by the real samples and the decision is flushed
+            let segment = self.process_partial_window(audio)?;
+            if let Some(mut ts) = segment.or_else(|| self.flush()) {
+                if keep_segment(&mut ts, &self.config, min_speech_duration_ms, max_speech_duration_s) {
+                    timestamps.push(ts);
+                }
+            }
+            return Ok(timestamps);
+        }
+
+        if self.config.bidirectional {
+            let forward = self.offline_probabilities(audio)?;
+
+            // Score the same whole windows again back to front; the reversed
+            // audio is a separate stream, so it starts from a cleared context
+            let after = self.model.context().to_owned();
+            self.reset_model();
+            let whole = audio.len() / chunk_size * chunk_size;
+            let reversed = audio.slice(ndarray::s![..whole;-1]);
+            let backward = self.offline_probabilities(&reversed)?;
+            self.model.set_context(after)?;
+
+            let mut probs = combine_bidirectional(&forward, &backward);
+            self.config.fill_gaps(&mut probs);
+            for prob in probs {
+                if let Some(mut ts) = self.state.advance(&self.config, prob, chunk_size) {
+                    events::segment_detected(&ts);
+                    if keep_segment(&mut ts, &self.config, min_speech_duration_ms, max_speech_duration_s) {
+                        timestamps.push(ts);
+                    }
+                }
+            }
+            return Ok(timestamps);
+        }
+
+        if self.config.half_window_refinement {
+            let context = self.model.context().to_owned();
+            let aligned = self.offline_probabilities(audio)?;
+
+            // Score the same audio from the same context, half a window later
+            self.model.set_context(context)?;
+            let shifted = audio.slice(ndarray::s![chunk_size / 2..]);
+            let offset = self.offline_probabilities(&shifted)?;
+
+            for mut ts in advance_offset_passes(&mut self.state, &self.config, &aligned, &offset) {
+                events::segment_detected(&ts);
+                if keep_segment(&mut ts, &self.config, min_speech_duration_ms, max_speech_duration_s) {
+                    timestamps.push(ts);
+                }
+            }
+            return Ok(timestamps);
+        }
+
+        if self.config.gap_fill_windows > 0 {
+            // Gap filling needs the whole probability sequence up front, so
+            // inference runs first
+            let mut probs = self.offline_probabilities(audio)?;
+            self.config.fill_gaps(&mut probs);
+
+            for prob in probs {
+                if let Some(mut ts) = self.state.advance(&self.config, prob, chunk_size) {
+                    events::segment_detected(&ts);
+                    if keep_segment(&mut ts, &self.config, min_speech_duration_ms, max_speech_duration_s) {
+                        timestamps.push(ts);
+                    }
+                }
+            }
+            return Ok(timestamps);
+        }
+        
+        // Process audio chunks one at a time
+        let mut i = 0;
+        while i < audio.len() {
+            let end = (i + chunk_size).min(audio.len());
+            if end - i < chunk_size {
+                break;
+            }
+
+            debug!("Processing chunk at position {}", i);
+            
+            // Process the chunk
+            let window = audio.slice(ndarray::s![i..end]);
+            if let Some(mut ts) = self.process_chunk(&window)? {
+                if keep_segment(&mut ts, &self.config, min_speech_duration_ms, max_speech_duration_s) {
+                    timestamps.push(ts);
+                }
+            }
+
+            i = end;
+        }
+
+        Ok(timestamps)
+    }
+
+    /// Score every whole window of the audio ahead of segmentation
+    /// 
+    /// The model context is cleared wherever window-by-window detection
+    /// would emit a segment, so the probabilities match the ones
+    /// [`VADIterator::process_chunk`] computes for the same audio. Emission
+    /// is tracked on a copy of the segment state, which is left untouched.
+    fn offline_probabilities(&mut self, audio: &ArrayView1<f32>) -> Result<Vec<f32>> {
+        let sr = self.config.sampling_rate;
+        let chunk_size = self.window_size();
+        let mut state = self.state.clone();
+        let mut probs = Vec::with_capacity(audio.len() / chunk_size);
+
+        for window in audio.exact_chunks(chunk_size) {
+            let classes = self.chunk_classes(&window, sr)?;
+            let prob = self.class_probability(&classes)?;
+            if state.advance(&self.config, prob, chunk_size).is_some() {
+                self.reset_model();
+            }
+            probs.push(prob);
+        }
+        Ok(probs)
+    }
+
+    /// Cache the probability pass of a clip in an analysis file
+    /// 
+    /// Runs the expensive part of detection, inference, once (see
+    /// [`VADIterator::probabilities`]) and writes the per-window
+    /// probabilities with the detector configuration as JSON. Segmentation
+    /// can then be repeated cheaply with different parameters, without the
+    /// model, through [`segment_from_analysis`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Complete audio clip to analyze
+    /// * `sr` - Sampling rate of the audio
+    /// * `path` - File to write the analysis to
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * Model inference fails
+    /// * The file cannot be written
+    pub fn dump_analysis<P: AsRef<std::path::Path>>(
+        &mut self,
+        audio: &ArrayView1<f32>,
+        sr: u32,
+        path: P,
+    ) -> Result<()> {
+        let probabilities = self
+            .probabilities(audio, sr)
+            .map(|r| r.map(|(_, prob)| prob))
+            .collect::<Result<Vec<f32>>>()?;
+        let analysis = ProbabilityAnalysis {
+            sampling_rate: sr,
+            window_size: window_size_for(sr),
+            audio_samples: audio.len(),
+            probabilities,
+            config: self.config.clone(),
+        };
+        analysis.save(path)
+    }
+
+    /// Detect speech segments and extract the audio of each one
+    /// 
+    /// Bundles [`VADIterator::get_speech_timestamps`] and extraction for the
+    /// common case where both are wanted. Segment length limits come from
+    /// [`VadConfig::min_speech_samples`] and [`VadConfig::max_speech_samples`].
+    /// Each segment's audio is copied, so prefer the timestamp-only methods
+    /// when the audio isn't needed.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Complete audio to process
+    /// * `sr` - Sampling rate of the audio, which must match the configured rate
+    /// 
+    /// # Returns
+    /// 
+    /// Every detected segment together with its samples
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * `sr` differs from the configured sampling rate
+    /// * The audio is rejected by [`VADIterator::get_speech_timestamps`]
+    /// * Model inference fails
+    pub fn detect_with_audio(
+        &mut self,
+        audio: &ArrayView1<f32>,
+        sr: u32,
+    ) -> Result<Vec<SegmentWithAudio>> {
+        if sr != self.config.sampling_rate {
+            return Err(Error::InvalidInput(format!(
+                "Audio at {} Hz passed to a detector configured for {} Hz",
+                sr, self.config.sampling_rate
+            )));
+        }
+
+        let (min_silence_ms, pad_ms) = (self.config.min_silence_duration_ms, self.config.speech_pad_ms);
+        let timestamps =
+            self.get_speech_timestamps(audio, 0, f32::INFINITY, min_silence_ms, pad_ms)?;
+
+        Ok(timestamps
+            .into_iter()
+            .map(|ts| {
+                let end = ts.end_sample.min(audio.len());
+                let start = ts.start_sample.min(end);
+                let audio = audio.slice(ndarray::s![start..end]).to_owned();
+                SegmentWithAudio { timestamps: ts, audio }
+            })
+            .collect())
+    }
+
+    /// Detect speech in an audio file
+    /// 
+    /// The file is read and, if needed, resampled to the configured rate with
+    /// [`crate::utils::read_audio_opts`]. Segment length limits come from
+    /// [`VadConfig::min_speech_samples`] and [`VadConfig::max_speech_samples`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Audio file to process
+    /// 
+    /// # Returns
+    /// 
+    /// The file's segments, with a one-line summary for batch logs
+    /// available through [`VadResult::summary_line`]
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The file cannot be read
+    /// * Detection fails, see [`VADIterator::get_speech_timestamps`]
+    pub fn detect_file<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<VadResult> {
+        let sr = self.config.sampling_rate;
+        let options = crate::utils::ReadOptions {
+            sampling_rate: Some(sr),
+            resample: true,
+            ..Default::default()
+        };
+        let audio = crate::utils::read_audio_opts(path.as_ref(), options)?;
+        let (min_silence_ms, pad_ms) = (self.config.min_silence_duration_ms, self.config.speech_pad_ms);
+        let segments =
+            self.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, min_silence_ms, pad_ms)?;
+
+        Ok(VadResult {
+            path: path.as_ref().to_path_buf(),
+            duration_s: audio.len() as f32 * self.config.time_scale / sr as f32,
+            segments,
+        })
+    }
+
+    /// Stream a WAV file through the detector without loading it
+    /// 
+    /// The file is memory-mapped with [`crate::utils::read_audio_mmap`] and
+    /// its windows are read ahead on a background thread (see
+    /// [`crate::utils::MappedWav::prefetch_windows`]), so reading overlaps
+    /// with inference and memory stays bounded for files of any length.
+    /// Windows run through [`VADIterator::process_chunk`] in order and a
+    /// segment still open at the end is finalized with
+    /// [`VADIterator::flush`]. As with [`VADIterator::process_until`], a
+    /// trailing partial window is ignored and segments are not filtered by
+    /// duration.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Path to a 16-bit PCM or 32-bit float WAV file
+    /// * `depth` - Number of windows read ahead of inference (at least 1)
+    /// 
+    /// # Returns
+    /// 
+    /// The segments detected in the file, in order
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The file cannot be mapped or its format is not supported
+    /// * The file's sampling rate differs from the configured one
+    /// * Model inference fails
+    pub fn stream_wav<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+        depth: usize,
+    ) -> Result<Vec<SpeechTimestamps>> {
+        let wav = crate::utils::read_audio_mmap(path.as_ref())?;
+        if wav.sampling_rate() != self.config.sampling_rate {
+            return Err(Error::InvalidInput(format!(
+                "{} is sampled at {} Hz but the detector is configured for {} Hz",
+                path.as_ref().display(),
+                wav.sampling_rate(),
+                self.config.sampling_rate
+            )));
+        }
+
+        let mut segments = Vec::new();
+        for window in wav.prefetch_windows(self.window_size(), depth) {
+            if let Some(ts) = self.process_chunk(&window.view())? {
+                segments.push(ts);
+            }
+        }
+        segments.extend(self.flush());
+        Ok(segments)
+    }
+
+    /// Get speech timestamps for multi-channel audio
+    /// 
+    /// The channels are reduced to mono with [`crate::utils::downmix_channels`]
+    /// and the result runs through [`VADIterator::get_speech_timestamps`].
+    /// To detect on every channel separately, use [`crate::MultiChannelVad`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Complete audio with shape `[channels, samples]`
+    /// * `mode` - How the channels are reduced to mono
+    /// * `min_speech_duration_ms` - Minimum duration of speech segments
+    /// * `max_speech_duration_s` - Maximum duration of speech segments
+    /// 
+    /// # Returns
+    /// 
+    /// Vector of speech timestamps for all detected segments
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The channels cannot be reduced with `mode`
+    /// * The mono audio is rejected by [`VADIterator::get_speech_timestamps`]
+    /// * Model inference fails
+    pub fn get_speech_timestamps_multichannel(
+        &mut self,
+        audio: &ArrayView2<f32>,
+        mode: ChannelMode,
+        min_speech_duration_ms: u32,
+        max_speech_duration_s: f32,
+    ) -> Result<Vec<SpeechTimestamps>> {
+        let mono = crate::utils::downmix_channels(audio, mode)?;
+        let (min_silence_ms, pad_ms) = (self.config.min_silence_duration_ms, self.config.speech_pad_ms);
+        self.get_speech_timestamps(
+            &mono.view(),
+            min_speech_duration_ms,
+            max_speech_duration_s,
+            min_silence_ms,
+            pad_ms,
+        )
+    }
+
+    /// Get speech timestamps for planar stereo audio by detecting on its mono mix
+    /// 
+    /// # Arguments
+    /// 
+    /// * `left` - Samples of the left channel
+    /// * `right` - Samples of the right channel
+    /// * `min_speech_duration_ms` - Minimum duration of speech segments
+    /// * `max_speech_duration_s` - Maximum duration of speech segments
+    /// 
+    /// # Returns
+    /// 
+    /// Vector of speech timestamps for all detected segments
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The channels have different lengths
+    /// * The mono audio is rejected by [`VADIterator::get_speech_timestamps`]
+    /// * Model inference fails
+    pub fn get_speech_timestamps_planar(
+        &mut self,
+        left: &[f32],
+        right: &[f32],
+        min_speech_duration_ms: u32,
+        max_speech_duration_s: f32,
+    ) -> Result<Vec<SpeechTimestamps>> {
+        let mono = crate::utils::downmix_planar(left, right)?;
+        let (min_silence_ms, pad_ms) = (self.config.min_silence_duration_ms, self.config.speech_pad_ms);
+        self.get_speech_timestamps(
+            &mono.view(),
+            min_speech_duration_ms,
+            max_speech_duration_s,
+            min_silence_ms,
+            pad_ms,
+        )
+    }
+
+    /// Get speech timestamps for an entire audio file using batched inference
+    /// 
+    /// The audio is split into consecutive windows which are sent to the model
+    /// up to `batch_size` at a time. The batch is one stream, not independent
+    /// ones: the first row continues from the carried context and every other
+    /// row from the tail of the window before it, so the probabilities are the
+    /// ones window-by-window detection computes. Where a segment is emitted
+    /// and the model context is cleared, the next batch starts at the
+    /// following window. A trailing partial window is ignored, as in
+    /// [`VADIterator::get_speech_timestamps`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Complete audio file to process
+    /// * `batch_size` - Number of windows per inference call
+    /// * `min_speech_duration_ms` - Minimum duration of speech segments
+    /// * `max_speech_duration_s` - Maximum duration of speech segments
+    /// 
+    /// # Returns
+    /// 
+    /// Vector of speech timestamps for all detected segments
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The batch size is zero
+    /// * The audio is shorter than [`VadConfig::min_audio_windows`] windows
+    /// * Model inference fails
+    pub fn get_speech_timestamps_batched(
+        &mut self,
+        audio: &ArrayView1<f32>,
+        batch_size: usize,
+        min_speech_duration_ms: u32,
+        max_speech_duration_s: f32,
+    ) -> Result<Vec<SpeechTimestamps>> {
+        if batch_size == 0 {
+            return Err(Error::InvalidInput("Batch size must be at least 1".into()));
+        }
+        self.check_audio_length(audio.len())?;
+
+        let mut timestamps = Vec::new();
+        let sr = self.config.sampling_rate;
+        let chunk_size = self.window_size();
+        let num_chunks = audio.len() / chunk_size;
+        let tail = chunk_size - CONTEXT_SIZE;
+
+        let mut first = 0;
+        while first < num_chunks {
+            let rows = batch_size.min(num_chunks - first);
+            debug!("Processing batch of {} chunks at chunk {}", rows, first);
+
+            let mut batch = Array2::zeros((rows, chunk_size));
+            for (r, mut row) in batch.rows_mut().into_iter().enumerate() {
+                let start = (first + r) * chunk_size;
+                row.assign(&audio.slice(ndarray::s![start..start + chunk_size]));
+                if let Some(threshold) = self.config.limiter_threshold {
+                    row.mapv_inplace(|v| crate::utils::soft_limit_sample(v, threshold));
+                }
+            }
+
+            // Chain the rows: each one continues from the window before it
+            let mut context = Array2::zeros((rows, CONTEXT_SIZE));
+            if self.model.context().nrows() == 1 {
+                context.row_mut(0).assign(&self.model.context().row(0));
+            }
+            for r in 1..rows {
+                context.row_mut(r).assign(&batch.slice(ndarray::s![r - 1, tail..]));
+            }
+            self.model.set_context(context)?;
+
+            let probs = self.model.process_batch_classes(&batch, sr)?;
+            let mut scored = rows;
+            for (r, row) in probs.rows().into_iter().enumerate() {
+                let prob = self.class_probability(&row.to_vec())?;
+                if let Some(mut ts) = self.advance(prob, chunk_size) {
+                    if keep_segment(&mut ts, &self.config, min_speech_duration_ms, max_speech_duration_s) {
+                        timestamps.push(ts);
+                    }
+                    if !self.config.continuous_file {
+                        // The rest of the batch was scored from a stale context
+                        scored = r + 1;
+                        break;
+                    }
+                }
+            }
+
+            if scored == rows {
+                self.model.set_context(batch.slice(ndarray::s![rows - 1..rows, tail..]).to_owned())?;
+            }
+            first += scored;
+        }
+
+        Ok(timestamps)
+    }
+
+    /// Process a batch of audio chunks and return speech timestamps if detected
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Batch of audio chunks to process (each chunk must be 512 samples for 16kHz)
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(Some(timestamps))` if speech is detected, `Ok(None)` otherwise
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The input chunk size is invalid
+    /// * Model inference fails
+    pub fn process_batch(&mut self, x: &Array2<f32>) -> Result<Option<Vec<SpeechTimestamps>>> {
+        let probs = self.batch_classes(x)?;
+        let probs = probs
+            .rows()
+            .into_iter()
+            .map(|row| self.class_probability(&row.to_vec()))
+            .collect::<Result<Vec<f32>>>()?;
+        let mut results = Vec::new();
+
+        for &prob in probs.iter() {
+            if let Some(ts) = self.advance(prob, x.ncols()) {
+                results.push(ts);
+            }
+        }
+
+        Ok(if results.is_empty() { None } else { Some(results) })
+    }
+
+    /// Classify each chunk of a batch as speech or non-speech
+    /// 
+    /// Unlike [`VADIterator::process_batch`], no segments are built and the
+    /// segment state and timeline are left untouched; each row is simply
+    /// compared against the configured threshold. The model context still
+    /// advances, as with any inference.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `x` - Batch of audio chunks to classify (each chunk must be 512 samples for 16kHz)
+    /// 
+    /// # Returns
+    /// 
+    /// One speech/non-speech decision per input row
     /// 
     /// # Errors
     /// 
     /// Returns an error if:
-    /// * The audio data is invalid
+    /// * The input chunk size is invalid
     /// * Model inference fails
-    pub fn get_speech_timestamps(
+    pub fn classify_batch(&mut self, x: &Array2<f32>) -> Result<Vec<bool>> {
+        let probs = self.batch_classes(x)?;
+        probs
+            .rows()
+            .into_iter()
+            .map(|row| Ok(self.config.is_speech(self.class_probability(&row.to_vec())?)))
+            .collect()
+    }
+
+    /// Lazily compute the speech probability of each window of the audio
+    /// 
+    /// Windows are run through the model one at a time, in order, as the
+    /// iterator is advanced, so the model context carries across windows just
+    /// as with [`VADIterator::process_chunk`]. The segment state and timeline
+    /// are not affected. A trailing partial window is ignored.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Audio to analyze
+    /// * `sr` - Sampling rate of the audio
+    /// 
+    /// # Returns
+    /// 
+    /// An iterator of `(window, probability)` pairs
+    pub fn probabilities<'a>(
+        &'a mut self,
+        audio: &'a ArrayView1<'_, f32>,
+        sr: u32,
+    ) -> impl Iterator<Item = Result<(Array1<f32>, f32)>> + 'a {
+        audio
+            .exact_chunks(window_size_for(sr))
+            .into_iter()
+            .map(move |window| {
+                let probs = self.chunk_classes(&window, sr)?;
+                let prob = self.class_probability(&probs)?;
+                Ok((window.to_owned(), prob))
+            })
+    }
+
+    /// Find when speech first starts in a clip
+    /// 
+    /// A lightweight endpointing primitive: windows are fed in order through
+    /// the same state machine as [`VADIterator::poll_first_speech`], starting
+    /// from a fresh timeline, and the scan stops as soon as a segment opens,
+    /// so the rest of the clip is never run through the model. The onset
+    /// therefore honours [`VadConfig::initial_skip_ms`] and the configured
+    /// lookback exactly like streaming detection. The segment state and the
+    /// model context of the iterator are restored afterwards.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Audio to scan
+    /// * `sr` - Sampling rate of the audio, which must match the configured rate
+    /// 
+    /// # Returns
+    /// 
+    /// Onset time in seconds relative to the start of `audio`, scaled by
+    /// [`VadConfig::time_scale`], or `None` if the clip contains no speech
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * `sr` differs from the configured sampling rate
+    /// * Model inference fails
+    pub fn first_speech_time(&mut self, audio: &ArrayView1<f32>, sr: u32) -> Result<Option<f32>> {
+        if sr != self.config.sampling_rate {
+            return Err(Error::InvalidInput(format!(
+                "Audio at {} Hz passed to a detector configured for {} Hz",
+                sr, self.config.sampling_rate
+            )));
+        }
+
+        let context = self.model.context().to_owned();
+        let state = std::mem::take(&mut self.state);
+        let onset = self.scan_first_speech(audio);
+        self.state = state;
+        self.model.set_context(context)?;
+        onset
+    }
+
+    /// Step through whole windows until a segment opens
+    fn scan_first_speech(&mut self, audio: &ArrayView1<f32>) -> Result<Option<f32>> {
+        for window in audio.exact_chunks(self.window_size()) {
+            self.step(&window)?;
+            if let Some(start) = self.state.speech_start {
+                return Ok(Some(start));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Compute a per-window feature matrix for training downstream models
+    /// 
+    /// Each row describes one window of the detector's grid (consecutive,
+    /// non-overlapping windows from sample 0, trailing partial window
+    /// ignored), with the columns:
+    /// 
+    /// 0. `time_s` - window start time in seconds, scaled by
+    ///    [`VadConfig::time_scale`]
+    /// 1. `probability` - speech probability in `[0, 1]`
+    /// 2. `rms` - RMS amplitude of the window, in the units of `audio`
+    /// 
+    /// Inference and energy are computed in a single pass, so the rows are
+    /// aligned by construction.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Audio data to analyze
+    /// * `sr` - Sampling rate of the audio
+    /// 
+    /// # Returns
+    /// 
+    /// Array of shape `[windows, 3]`
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if model inference fails
+    pub fn feature_matrix(&mut self, audio: &ArrayView1<f32>, sr: u32) -> Result<Array2<f32>> {
+        let window_s = window_size_for(sr) as f32 * self.config.time_scale / sr as f32;
+        let mut features = Vec::new();
+
+        for (i, result) in self.probabilities(audio, sr).enumerate() {
+            let (window, prob) = result?;
+            let rms = (window.iter().map(|s| s * s).sum::<f32>() / window.len() as f32).sqrt();
+            features.extend_from_slice(&[i as f32 * window_s, prob, rms]);
+        }
+
+        let rows = features.len() / 3;
+        Array2::from_shape_vec((rows, 3), features)
+            .map_err(|e| Error::AudioProcessing(e.to_string()))
+    }
+
+    /// Run offline detection and keep every intermediate step
+    /// 
+    /// A debugging aid for tuning: comparing the raw window triggers, the
+    /// initial segments they form and the final post-processed segments
+    /// shows which step moved a boundary. The final segments are exactly the
+    /// ones [`VADIterator::get_speech_timestamps`] returns for the clip, so
+    /// inference runs twice, once for the raw probabilities and once for
+    /// detection. Times are relative to the start of `audio`; the segment
+    /// state of the iterator and the model context are left untouched.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Complete audio clip to analyze
+    /// * `sr` - Sampling rate of the audio, which must match the configured rate
+    /// * `min_speech_duration_ms` - Minimum duration of final speech segments
+    /// * `max_speech_duration_s` - Maximum duration of final speech segments
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if:
+    /// * The audio is empty
+    /// * `sr` doesn't match the configured sampling rate
+    /// * The audio is shorter than [`VadConfig::min_audio_windows`] windows
+    /// * Model inference fails
+    pub fn detect_detailed(
         &mut self,
         audio: &ArrayView1<f32>,
+        sr: u32,
         min_speech_duration_ms: u32,
         max_speech_duration_s: f32,
-        _min_silence_duration_ms: u32,
-        _speech_pad_ms: u32,
-    ) -> Result<Vec<SpeechTimestamps>> {
-        let mut timestamps = Vec::new();
-        let chunk_size = if self.sampling_rate == 16000 { 512 } else { 256 };
-        
-        // Process audio chunks one at a time
-        let mut i = 0;
-        while i < audio.len() {
-            let end = (i + chunk_size).min(audio.len());
-            if end - i < chunk_size {
-                break;
-            }
+    ) -> Result<DetectionDetail> {
+        if audio.is_empty() {
+            return Err(Error::InvalidInput(
+                "Audio buffer is empty; pass at least one sample".into(),
+            ));
+        }
+        if sr != self.config.sampling_rate {
+            return Err(Error::InvalidInput(format!(
+                "Audio at {} Hz passed to a detector configured for {} Hz",
+                sr, self.config.sampling_rate
+            )));
+        }
 
-            debug!("Processing chunk at position {}", i);
-            
-            // Process the chunk
-            let window = audio.slice(ndarray::s![i..end]);
-            if let Some(ts) = self.process_chunk(&window)? {
-                if ts.end - ts.start >= min_speech_duration_ms as f32 / 1000.0
-                    && ts.end - ts.start <= max_speech_duration_s
-                {
-                    timestamps.push(ts);
+        let context = self.model.context().to_owned();
+        let state = std::mem::take(&mut self.state);
+        let detail =
+            self.detect_detailed_from_start(audio, min_speech_duration_ms, max_speech_duration_s);
+        self.state = state;
+        self.model.set_context(context)?;
+        detail
+    }
+
+    /// Both passes of [`VADIterator::detect_detailed`], each from the current context
+    fn detect_detailed_from_start(
+        &mut self,
+        audio: &ArrayView1<f32>,
+        min_speech_duration_ms: u32,
+        max_speech_duration_s: f32,
+    ) -> Result<DetectionDetail> {
+        let context = self.model.context().to_owned();
+        let probabilities = self.offline_probabilities(audio)?;
+        let triggers: Vec<bool> = probabilities.iter().map(|&p| self.config.is_speech(p)).collect();
+
+        let window_s =
+            self.window_size() as f32 * self.config.time_scale / self.config.sampling_rate as f32;
+        let mut initial_segments = Vec::new();
+        let mut run_start = None;
+        for (i, &triggered) in triggers.iter().chain(std::iter::once(&false)).enumerate() {
+            match (triggered, run_start) {
+                (true, None) => run_start = Some(i),
+                (false, Some(first)) => {
+                    let run = &probabilities[first..i];
+                    initial_segments.push(SpeechTimestamps {
+                        start: first as f32 * window_s,
+                        end: i as f32 * window_s,
+                        confidence: run.iter().sum::<f32>() / run.len() as f32,
+                        peak_confidence: run.iter().copied().fold(0.0, f32::max),
+                        split_reason: SplitReason::Silence,
+                        start_sample: first * self.window_size(),
+                        end_sample: i * self.window_size(),
+                    });
+                    run_start = None;
                 }
+                _ => {}
             }
+        }
 
-            i = end;
+        self.model.set_context(context)?;
+        self.state = VadState::default();
+        let final_segments =
+            self.detect_offline(audio, min_speech_duration_ms, max_speech_duration_s)?;
+        Ok(DetectionDetail { probabilities, triggers, initial_segments, final_segments })
+    }
+
+    /// Triage a clip as mostly speech, mostly silence, or uncertain
+    /// 
+    /// Runs a probability pass over the whole clip (see
+    /// [`VADIterator::probabilities`]) and classifies it from the fraction of
+    /// windows above the threshold and the fraction of ambiguous windows, whose
+    /// probability lies within 0.15 of the threshold:
+    /// 
+    /// * More than half of the windows ambiguous: [`ClipClass::Uncertain`]
+    /// * At least half of the windows speech: [`ClipClass::MostlySpeech`]
+    /// * At most 10% of the windows speech: [`ClipClass::MostlySilence`]
+    /// * Anything else: [`ClipClass::Uncertain`]
+    /// 
+    /// Music and other non-speech sounds typically end up as uncertain, since
+    /// the model hovers around the threshold on them. A clip shorter than one
+    /// window is reported as mostly silence.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Clip to classify
+    /// * `sr` - Sampling rate of the audio
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if model inference fails
+    pub fn classify_clip(&mut self, audio: &ArrayView1<f32>, sr: u32) -> Result<ClipClass> {
+        let threshold = self.config.effective_threshold();
+        let probs = self
+            .probabilities(audio, sr)
+            .map(|r| r.map(|(_, prob)| prob))
+            .collect::<Result<Vec<f32>>>()?;
+
+        if probs.is_empty() {
+            return Ok(ClipClass::MostlySilence);
         }
 
-        Ok(timestamps)
+        let total = probs.len() as f32;
+        let speech = probs.iter().filter(|&&p| self.config.is_speech(p)).count() as f32 / total;
+        let ambiguous = probs.iter().filter(|&&p| (p - threshold).abs() < 0.15).count() as f32 / total;
+
+        Ok(if ambiguous > 0.5 {
+            ClipClass::Uncertain
+        } else if speech >= 0.5 {
+            ClipClass::MostlySpeech
+        } else if speech <= 0.1 {
+            ClipClass::MostlySilence
+        } else {
+            ClipClass::Uncertain
+        })
     }
 
-    /// Process a batch of audio chunks and return speech timestamps if detected
+    /// Suggest detector parameters from a probability pass over a recording
+    /// 
+    /// A data-driven starting point for tuning, better suited to the
+    /// recording than the fixed defaults but not an optimum; see
+    /// [`suggest_parameters_from_probs`] for how the values are chosen. The
+    /// model context is restored afterwards and the segment state is left
+    /// untouched.
     /// 
     /// # Arguments
     /// 
-    /// * `x` - Batch of audio chunks to process (each chunk must be 512 samples for 16kHz)
+    /// * `audio` - Representative audio, ideally several seconds of speech
+    /// * `sr` - Sampling rate of the audio
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if model inference fails
+    pub fn suggest_parameters(
+        &mut self,
+        audio: &ArrayView1<f32>,
+        sr: u32,
+    ) -> Result<SuggestedParameters> {
+        let context = self.model.context().to_owned();
+        let probs = self
+            .probabilities(audio, sr)
+            .map(|r| r.map(|(_, prob)| prob))
+            .collect::<Result<Vec<f32>>>();
+        self.model.set_context(context)?;
+
+        let config = VadConfig { sampling_rate: sr, ..self.config.clone() };
+        Ok(suggest_parameters_from_probs(&probs?, &config))
+    }
+
+    /// Get the frame-level speech decisions of a clip as run-length encoded runs
+    /// 
+    /// Runs a probability pass over the whole clip (see
+    /// [`VADIterator::probabilities`]) and thresholds each window. The runs
+    /// are an exact, compact form of the per-window decisions; expand them
+    /// with [`crate::utils::rle_decode`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio` - Complete audio clip to analyze
+    /// * `sr` - Sampling rate of the audio
     /// 
     /// # Returns
     /// 
-    /// `Ok(Some(timestamps))` if speech is detected, `Ok(None)` otherwise
+    /// `(is_speech, window_count)` runs in order, alternating between speech and silence
     /// 
     /// # Errors
     /// 
-    /// Returns an error if:
-    /// * The input chunk size is invalid
-    /// * Model inference fails
-    pub fn process_batch(&mut self, x: &Array2<f32>) -> Result<Option<Vec<SpeechTimestamps>>> {
-        let probs = self.model.process_batch(x, self.sampling_rate)?;
-        let mut results = Vec::new();
-        let time_per_sample = 1.0 / self.sampling_rate as f32;
-        let chunk_duration = (x.ncols() as f32) * time_per_sample;
+    /// Returns an error if model inference fails
+    pub fn rle_decisions(&mut self, audio: &ArrayView1<f32>, sr: u32) -> Result<Vec<(bool, u32)>> {
+        let probs = self
+            .probabilities(audio, sr)
+            .map(|r| r.map(|(_, prob)| prob))
+            .collect::<Result<Vec<f32>>>()?;
+        let decisions: Vec<bool> = probs.iter().map(|&p| self.config.is_speech(p)).collect();
+        Ok(crate::utils::rle_encode(&decisions))
+    }
+
+    /// Compute how many windows [`VADIterator::get_speech_timestamps`] processes
+    /// 
+    /// Useful to pre-allocate buffers or size a progress indicator before
+    /// processing starts. See [`crate::utils::chunk_count`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `audio_len` - Length of the audio in samples
+    pub fn chunk_count(&self, audio_len: usize) -> usize {
+        let chunk_size = self.window_size();
+        crate::utils::chunk_count(audio_len, chunk_size, chunk_size)
+    }
+}
 
-        for (i, &prob) in probs.iter().enumerate() {
-            let current_time = (i as f32 + 1.0) * chunk_duration;
+/// Segment window probabilities, flagging but keeping over-long segments
+fn segment_windows(probs: &[f32], config: &VadConfig) -> Vec<SpeechTimestamps> {
+    let chunk_size = window_size_for(config.sampling_rate);
+    let mut probs = probs.to_vec();
+    config.fill_gaps(&mut probs);
 
-            if prob >= self.threshold {
-                if self.speech_start.is_none() {
-                    self.speech_start = Some(current_time - chunk_duration);
-                }
-                self.speech_end = Some(current_time);
-            } else if self.speech_start.is_some() {
-                let silence_duration = current_time - self.speech_end.unwrap();
-                let silence_duration_ms = (silence_duration * 1000.0) as u32;
-
-                if silence_duration_ms >= self.min_silence_duration_ms {
-                    let start = self.speech_start.unwrap();
-                    let end = self.speech_end.unwrap() + (self.speech_pad_ms as f32 / 1000.0);
-                    results.push(SpeechTimestamps { start, end });
-                    self.reset();
-                }
+    let mut state = VadState::default();
+    probs
+        .into_iter()
+        .filter_map(|prob| state.advance(config, prob, chunk_size))
+        .map(|mut ts| {
+            if exceeds_max_duration(&ts, config, f32::INFINITY) {
+                ts.split_reason = SplitReason::MaxDuration;
             }
+            ts
+        })
+        .collect()
+}
+
+/// Turn precomputed window probabilities into final speech segments
+/// 
+/// The public seam between inference and interpretation: runs the complete
+/// post-processing of offline detection with [`VADIterator`] (gap filling,
+/// thresholding, hangover, minimum silence, padding and the segment length
+/// limits) on probabilities from any source, e.g. a custom model or an
+/// external ONNX runtime. Segments are filtered by
+/// [`VadConfig::min_speech_samples`] and [`VadConfig::max_speech_samples`];
+/// segments over the maximum are dropped, or kept and flagged with
+/// [`SplitReason::MaxDuration`] when [`VadConfig::keep_long_segments`] is set. As in offline detection, a
+/// segment still open at the end of the sequence is not reported.
+/// 
+/// Like offline detection, this never splits a segment that runs past
+/// [`VadConfig::max_speech_samples`]: a split needs a pause inside the
+/// segment, and without one any cut point would fall mid-speech. Use
+/// [`crate::utils::rechunk_segments`] to cut long segments into pieces.
+/// Refinements that need extra inference passes or the audio itself
+/// (`half_window_refinement`, `bidirectional`, `suppress_clipped_onsets`) are
+/// not applied; see [`segment_from_offset_passes`] and
+/// [`segment_from_bidirectional`] for the first two.
+/// 
+/// # Arguments
+/// 
+/// * `probs` - Speech probability per window of the model's window size at
+///   `config.sampling_rate`, in order
+/// * `config` - Detector configuration
+/// 
+/// # Returns
+/// 
+/// Speech timestamps for all segments finalized within the sequence
+pub fn segment_probabilities(probs: &[f32], config: &VadConfig) -> Vec<SpeechTimestamps> {
+    let mut segments = segment_windows(probs, config);
+    segments.retain_mut(|ts| keep_segment(ts, config, 0, f32::INFINITY));
+    segments
+}
+
+/// Run segmentation over a forward and a reversed pass of window probabilities
+/// 
+/// The model-free counterpart of offline detection with
+/// [`VadConfig::bidirectional`]: `forward` scores the windows in order and
+/// `backward` scores the same windows with the audio reversed, in the order
+/// that pass produced them (last window first). The passes are aligned and
+/// averaged before segmentation.
+/// 
+/// # Arguments
+/// 
+/// * `forward` - Speech probability of each window, in order
+/// * `backward` - Speech probability of each window of the reversed audio, in
+///   processing order
+/// * `config` - Detector configuration
+/// 
+/// # Returns
+/// 
+/// Speech timestamps for all segments finalized within the sequence
+pub fn segment_from_bidirectional(
+    forward: &[f32],
+    backward: &[f32],
+    config: &VadConfig,
+) -> Vec<SpeechTimestamps> {
+    segment_windows(&combine_bidirectional(forward, backward), config)
+}
+
+/// Average a forward pass with a reversed pass over the same windows
+/// 
+/// Both passes are expected to cover the same windows. If they don't, the
+/// windows missing from the shorter pass are dropped.
+fn combine_bidirectional(forward: &[f32], backward: &[f32]) -> Vec<f32> {
+    forward
+        .iter()
+        .zip(backward.iter().rev())
+        .map(|(f, b)| (f + b) / 2.0)
+        .collect()
+}
+
+/// Run segmentation over two passes of window probabilities offset by half a window
+/// 
+/// The model-free counterpart of offline detection with
+/// [`VadConfig::half_window_refinement`]: `aligned` scores windows starting
+/// at multiples of the window size, `offset` scores windows starting half a
+/// window later. The passes are interleaved into one sequence on a
+/// half-window grid, so boundaries are resolved to half a window.
+/// 
+/// # Arguments
+/// 
+/// * `aligned` - Speech probability of each window of the aligned pass, in order
+/// * `offset` - Speech probability of each window of the offset pass, in order
+/// * `config` - Detector configuration
+/// 
+/// # Returns
+/// 
+/// Speech timestamps for all segments finalized within the sequence
+pub fn segment_from_offset_passes(
+    aligned: &[f32],
+    offset: &[f32],
+    config: &VadConfig,
+) -> Vec<SpeechTimestamps> {
+    advance_offset_passes(&mut VadState::default(), config, aligned, offset)
+}
 
-            self.last_prob = prob;
+/// Feed interleaved aligned and offset passes through a state on a half-window grid
+fn advance_offset_passes(
+    state: &mut VadState,
+    config: &VadConfig,
+    aligned: &[f32],
+    offset: &[f32],
+) -> Vec<SpeechTimestamps> {
+    let half = window_size_for(config.sampling_rate) / 2;
+    let mut probs = Vec::with_capacity(aligned.len() + offset.len());
+    for (i, &prob) in aligned.iter().enumerate() {
+        probs.push(prob);
+        if let Some(&prob) = offset.get(i) {
+            probs.push(prob);
         }
+    }
+    config.fill_gaps(&mut probs);
 
-        Ok(if results.is_empty() { None } else { Some(results) })
+    // Each step covers the first half of its window; segments end where
+    // their last speech window ends
+    let end_shift = half as f32 * config.time_scale / config.sampling_rate as f32;
+    probs
+        .into_iter()
+        .filter_map(|prob| state.advance(config, prob, half))
+        .map(|mut ts| {
+            ts.end += end_shift;
+            ts.end_sample += half;
+            ts
+        })
+        .collect()
+}
+
+/// Run segmentation over a cached probability pass
+/// 
+/// Loads a file written by [`VADIterator::dump_analysis`] and segments its
+/// probabilities, so parameters can be tuned without re-running inference.
+/// Segmentation is as in [`segment_probabilities`], except that no segment is
+/// dropped for its length: segments longer than
+/// [`VadConfig::max_speech_samples`] are flagged with
+/// [`SplitReason::MaxDuration`]. The sampling rate is taken from the
+/// analysis; everything else comes from `config`.
+/// 
+/// # Arguments
+/// 
+/// * `path` - Analysis file to load
+/// * `config` - Segmentation parameters to apply
+/// 
+/// # Returns
+/// 
+/// Speech timestamps for all segments finalized within the analysis
+/// 
+/// # Errors
+/// 
+/// Returns an error if the file cannot be read or is not a valid analysis
+pub fn segment_from_analysis<P: AsRef<std::path::Path>>(
+    path: P,
+    config: &VadConfig,
+) -> Result<Vec<SpeechTimestamps>> {
+    let analysis = ProbabilityAnalysis::load(path)?;
+    let config = VadConfig { sampling_rate: analysis.sampling_rate, ..config.clone() };
+    Ok(segment_windows(&analysis.probabilities, &config))
+}
+
+/// Suggest detector parameters from precomputed window probabilities
+/// 
+/// The model-free counterpart of [`VADIterator::suggest_parameters`]:
+/// 
+/// * `threshold` splits the probabilities into two classes with Otsu's
+///   method, i.e. where the variance between the speech and non-speech
+///   windows is largest, clamped to `0.2..=0.8`. A recording on which the
+///   model is less decisive thus gets a threshold between its own clusters
+///   rather than the fixed 0.5.
+/// * `min_silence_duration_ms` is half the median pause between speech
+///   runs at that threshold, so typical pauses still split segments while
+///   shorter gaps inside words do not
+/// * `min_speech_duration_ms` is half the length of the shortest speech
+///   runs (the 10th percentile), to drop clicks without losing short words
+/// 
+/// Values the recording gives no evidence for (e.g. no pauses) keep those
+/// of `config`, or 250ms for the minimum speech duration. Sample-based
+/// limits in `config` still take precedence over the suggested duration.
+/// 
+/// # Arguments
+/// 
+/// * `probs` - Speech probability of each window, in order
+/// * `config` - Detector configuration to start from
+pub fn suggest_parameters_from_probs(probs: &[f32], config: &VadConfig) -> SuggestedParameters {
+    let mut suggested = SuggestedParameters {
+        config: config.clone(),
+        min_speech_duration_ms: 250,
+    };
+    if probs.len() < 2 {
+        return suggested;
+    }
+
+    suggested.config.threshold = otsu_threshold(probs).clamp(0.2, 0.8);
+    suggested.config.rate_thresholds.remove(&config.sampling_rate);
+
+    let voiced: Vec<bool> = probs.iter().map(|&p| suggested.config.is_speech(p)).collect();
+    let runs: Vec<&[bool]> = voiced.chunk_by(|a, b| a == b).collect();
+    let mut bursts: Vec<usize> = runs.iter().filter(|run| run[0]).map(|run| run.len()).collect();
+    // Silence before the first or after the last speech run is not a pause
+    let mut pauses: Vec<usize> = runs
+        .iter()
+        .enumerate()
+        .filter(|&(i, run)| !run[0] && i > 0 && i + 1 < runs.len())
+        .map(|(_, run)| run.len())
+        .collect();
+
+    let window_ms = WINDOW_MS as f32;
+    if !pauses.is_empty() {
+        pauses.sort_unstable();
+        let median_ms = pauses[pauses.len() / 2] as f32 * window_ms;
+        let min_silence_ms = (median_ms / 2.0).clamp(60.0, 400.0);
+        suggested.config.min_silence_duration_ms = (min_silence_ms / 10.0).round() as u32 * 10;
+    }
+    if !bursts.is_empty() {
+        bursts.sort_unstable();
+        let shortest_ms = bursts[bursts.len() / 10] as f32 * window_ms;
+        suggested.min_speech_duration_ms = (shortest_ms / 2.0).clamp(100.0, 300.0) as u32;
     }
-} 
\ No newline at end of file
+
+    suggested
+}
+
+/// Threshold separating values in `0.0..=1.0` into two classes by Otsu's method
+fn otsu_threshold(values: &[f32]) -> f32 {
+    const BINS: usize = 100;
+    let mut histogram = [0usize; BINS];
+    for &value in values {
+        histogram[((value.clamp(0.0, 1.0) * BINS as f32) as usize).min(BINS - 1)] += 1;
+    }
+
+    let total = values.len() as f32;
+    let sum: f32 = histogram.iter().enumerate().map(|(i, &count)| i as f32 * count as f32).sum();
+    let (mut weight_low, mut sum_low) = (0.0, 0.0);
+    // Empty bins between the classes give equally good splits; take the middle one
+    let (mut first_best, mut last_best, mut best_variance) = (BINS / 2, BINS / 2, 0.0);
+    for (i, &count) in histogram.iter().enumerate() {
+        weight_low += count as f32;
+        sum_low += i as f32 * count as f32;
+        let weight_high = total - weight_low;
+        if weight_low == 0.0 || weight_high == 0.0 {
+            continue;
+        }
+        let mean_low = sum_low / weight_low;
+        let mean_high = (sum - sum_low) / weight_high;
+        let variance = weight_low * weight_high * (mean_low - mean_high).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            (first_best, last_best) = (i + 1, i + 1);
+        } else if variance == best_variance {
+            last_best = i + 1;
+        }
+    }
+    (first_best + last_best) as f32 / 2.0 / BINS as f32
+}
+
+/// Compute stream statistics over precomputed window probabilities
+/// 
+/// The model-free counterpart of [`VADIterator::running_stats`]: returns the
+/// statistics a fresh iterator with the same configuration would report
+/// after streaming the given windows. Like [`events_from_probs`] and
+/// [`endpoint_from_probs`], and unlike offline segmentation, it does not
+/// apply gap filling (see [`VadConfig::fill_gaps`]).
+/// 
+/// # Arguments
+/// 
+/// * `probs` - Speech probability of each window, in order
+/// * `config` - Detector configuration
+/// 
+/// # Returns
+/// 
+/// Cumulative statistics after the last window
+pub fn stats_from_probs(probs: &[f32], config: &VadConfig) -> VadStats {
+    let chunk_size = window_size_for(config.sampling_rate);
+    let mut state = VadState::default();
+    for &prob in probs {
+        state.advance(config, prob, chunk_size);
+    }
+    state.stats()
+}
+
+/// Run streaming event detection over precomputed window probabilities
+/// 
+/// The model-free counterpart of [`VADIterator::process_chunk_events`]:
+/// returns the events a fresh iterator with the same configuration would
+/// report, in order.
+/// 
+/// # Arguments
+/// 
+/// * `probs` - Speech probability of each window, in order
+/// * `config` - Detector configuration
+/// 
+/// # Returns
+/// 
+/// Events of all windows
+pub fn events_from_probs(probs: &[f32], config: &VadConfig) -> Vec<VadEvent> {
+    let chunk_size = window_size_for(config.sampling_rate);
+    let mut state = VadState::default();
+    probs
+        .iter()
+        .flat_map(|&prob| {
+            let was_in_speech = state.in_speech();
+            let segment = state.advance(config, prob, chunk_size);
+            chunk_events(&state, was_in_speech, prob, segment)
+        })
+        .collect()
+}
+
+/// Build the events of one window from the state after it was processed
+fn chunk_events(
+    state: &VadState,
+    was_in_speech: bool,
+    prob: f32,
+    segment: Option<SpeechTimestamps>,
+) -> Vec<VadEvent> {
+    let mut events = Vec::new();
+    match (state.speech_start, state.speech_end) {
+        (Some(start), _) if !was_in_speech => {
+            events.push(VadEvent::SpeechStart { timestamp: start, probability: Some(prob) });
+        }
+        (Some(start), Some(current_end)) => {
+            events.push(VadEvent::SpeechOngoing { start, current_end });
+        }
+        _ => {}
+    }
+    if let Some(ts) = segment {
+        events.push(VadEvent::SpeechEnd { timestamp: ts.end, probability: Some(ts.confidence) });
+    }
+    events
+}
+
+/// Run utterance endpointing over precomputed window probabilities
+/// 
+/// The model-free counterpart of [`VADIterator::poll_endpoint`]: returns
+/// the endpoint state after each window, as a fresh iterator with the same
+/// configuration would report it.
+/// 
+/// # Arguments
+/// 
+/// * `probs` - Speech probability of each window, in order
+/// * `config` - Detector configuration
+/// 
+/// # Returns
+/// 
+/// One state per window
+pub fn endpoint_from_probs(probs: &[f32], config: &VadConfig) -> Vec<EndpointState> {
+    let chunk_size = window_size_for(config.sampling_rate);
+    let mut state = VadState::default();
+    probs
+        .iter()
+        .map(|&prob| {
+            state.advance(config, prob, chunk_size);
+            state.endpoint(config)
+        })
+        .collect()
+}
+
+/// Convert a window length in milliseconds to the model's window size in samples
+/// 
+/// The model only accepts one window size per sampling rate (32 ms: 512
+/// samples at 16kHz, 256 samples at 8kHz). This lets configuration be
+/// expressed in time while catching a window that the model cannot process.
+/// 
+/// # Arguments
+/// 
+/// * `window_ms` - Window length in milliseconds
+/// * `sampling_rate` - Sampling rate of the audio
+/// 
+/// # Returns
+/// 
+/// Window size in samples
+/// 
+/// # Errors
+/// 
+/// Returns an error if the window doesn't correspond to the model's window size
+pub fn window_from_ms(window_ms: u32, sampling_rate: u32) -> Result<usize> {
+    let samples = crate::utils::ms_to_samples(window_ms, sampling_rate);
+    let expected = window_size_for(sampling_rate);
+    if samples != expected {
+        return Err(Error::InvalidInput(format!(
+            "A {} ms window is {} samples at {} Hz, but the model requires {} samples ({} ms)",
+            window_ms,
+            samples,
+            sampling_rate,
+            expected,
+            expected as f32 * 1000.0 / sampling_rate as f32
+        )));
+    }
+    Ok(samples)
+}
+
+/// Check a finished segment against the minimum and maximum speech duration
+/// 
+/// Limits in samples set on the config take precedence over the durations
+/// and are compared with the segment's sample count, not its rounded times.
+fn keep_segment(
+    ts: &mut SpeechTimestamps,
+    config: &VadConfig,
+    min_speech_duration_ms: u32,
+    max_speech_duration_s: f32,
+) -> bool {
+    let duration = (ts.end - ts.start) / config.time_scale;
+
+    let long_enough = match config.min_speech_samples {
+        Some(min) => ts.sample_len() >= min,
+        None => duration >= min_speech_duration_ms as f32 / 1000.0,
+    };
+    if !exceeds_max_duration(ts, config, max_speech_duration_s) {
+        return long_enough;
+    }
+    ts.split_reason = SplitReason::MaxDuration;
+    long_enough && config.keep_long_segments
+}
+
+/// Whether a segment is longer than the maximum speech duration
+/// 
+/// [`VadConfig::max_speech_samples`] takes precedence over `max_speech_duration_s`.
+fn exceeds_max_duration(
+    ts: &SpeechTimestamps,
+    config: &VadConfig,
+    max_speech_duration_s: f32,
+) -> bool {
+    match config.max_speech_samples {
+        Some(max) => ts.sample_len() > max,
+        None => (ts.end - ts.start) / config.time_scale > max_speech_duration_s,
+    }
+}
+
+/// Reject a time scale that would collapse, reverse or blow up reported times
+fn check_time_scale(time_scale: f32) -> Result<()> {
+    if time_scale > 0.0 && time_scale.is_finite() {
+        Ok(())
+    } else {
+        Err(Error::InvalidInput(format!(
+            "Time scale {} is invalid; it must be a positive, finite factor",
+            time_scale
+        )))
+    }
+}
+
+/// Duration in milliseconds of a model window, the same at every sampling rate
+const WINDOW_MS: usize = 32;
+
+/// Number of samples the model expects per window at a sampling rate
+/// 
+/// This is the single source of window sizes for chunking, validation and
+/// tensor shapes. Whether the model accepts the rate at all is decided by
+/// [`crate::SUPPORTED_SAMPLING_RATES`].
+pub(crate) fn window_size_for(sampling_rate: u32) -> usize {
+    sampling_rate as usize * WINDOW_MS / 1000
+}
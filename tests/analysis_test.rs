@@ -0,0 +1,41 @@
+use silero_vad_rs::{segment_from_analysis, segment_probabilities, ProbabilityAnalysis, VadConfig};
+use tempfile::TempDir;
+
+#[test]
+fn test_analysis_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("clip.analysis.json");
+
+    let mut probabilities = vec![0.1, 0.8, 0.9, 0.9, 0.55, 0.4, 0.9];
+    probabilities.extend_from_slice(&[0.05; 10]);
+    let analysis = ProbabilityAnalysis {
+        sampling_rate: 16000,
+        window_size: 512,
+        audio_samples: 512 * probabilities.len() + 100,
+        probabilities,
+        config: VadConfig::default(),
+    };
+    analysis.save(&path).unwrap();
+    assert_eq!(ProbabilityAnalysis::load(&path).unwrap(), analysis);
+
+    // Re-segmenting with other parameters needs no model
+    for threshold in [0.5, 0.6] {
+        let config = VadConfig { threshold, speech_pad_ms: 0, ..Default::default() };
+        let from_file = segment_from_analysis(&path, &config).unwrap();
+        let direct = segment_probabilities(&analysis.probabilities, &config);
+
+        assert_eq!(from_file.len(), direct.len());
+        for (a, b) in from_file.iter().zip(&direct) {
+            assert_eq!((a.start, a.end), (b.start, b.end));
+        }
+    }
+}
+
+#[test]
+fn test_invalid_analysis_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("broken.json");
+    std::fs::write(&path, "not an analysis").unwrap();
+
+    assert!(segment_from_analysis(&path, &VadConfig::default()).is_err());
+}
@@ -0,0 +1,149 @@
+use ndarray::{Array1, ArrayView1};
+use silero_vad_rs::{SileroVAD, VADIterator};
+use tempfile::TempDir;
+
+fn vad(temp_dir: &TempDir) -> VADIterator {
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    VADIterator::new(model, 0.5, 16000, 100, 30).unwrap()
+}
+
+#[test]
+fn test_process_appended_matches_whole_buffer() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::from_shape_fn(16000 * 3, |i| {
+        let t = i as f32 / 16000.0;
+        if (1.0..2.0).contains(&t) {
+            0.5 * (t * 440.0 * std::f32::consts::TAU).sin()
+        } else {
+            0.0
+        }
+    });
+
+    let mut whole = vad(&temp_dir);
+    let expected = whole.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0).unwrap();
+
+    let mut incremental = vad(&temp_dir);
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for step in [700, 5000, 1, 12345].iter().cycle() {
+        if start >= audio.len() {
+            break;
+        }
+        let end = (start + step).min(audio.len());
+        let tail = audio.slice(ndarray::s![start..end]);
+        segments.extend(incremental.process_appended(&tail).unwrap());
+        start = end;
+    }
+
+    let bounds = |v: &[silero_vad_rs::SpeechTimestamps]| {
+        v.iter().map(|ts| (ts.start, ts.end)).collect::<Vec<_>>()
+    };
+    assert_eq!(bounds(&segments), bounds(&expected));
+    assert_eq!(incremental.processed_samples(), whole.processed_samples());
+}
+
+#[test]
+fn test_process_interleaved_matches_mono() {
+    let temp_dir = TempDir::new().unwrap();
+    let mono = Array1::from_shape_fn(16000, |i| 0.2 * (i as f32 / 7.0).sin());
+    let stereo: Vec<f32> = mono.iter().flat_map(|&s| [s, s]).collect();
+
+    let mut from_mono = vad(&temp_dir);
+    from_mono.process_appended(&mono.view()).unwrap();
+    let mut from_stereo = vad(&temp_dir);
+    for buffer in stereo.chunks(882) {
+        from_stereo.process_interleaved(buffer, 2).unwrap();
+    }
+
+    assert_eq!(from_stereo.processed_samples(), from_mono.processed_samples());
+    assert_eq!(from_stereo.model().context(), from_mono.model().context());
+    assert!(from_stereo.process_interleaved(&[0.0; 3], 2).is_err());
+}
+
+#[test]
+fn test_detect_windows_matches_stream() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::from_shape_fn(512 * 40, |i| {
+        let t = i as f32 / 16000.0;
+        if (0.3..0.9).contains(&t) {
+            0.5 * (t * 300.0 * std::f32::consts::TAU).sin()
+        } else {
+            0.0
+        }
+    });
+    let windows = audio.clone().into_shape((40, 512)).unwrap();
+
+    let mut streamed = vad(&temp_dir);
+    let expected = streamed.process_appended(&audio.view()).unwrap();
+    let mut windowed = vad(&temp_dir);
+    let segments = windowed.detect_windows(&windows).unwrap();
+
+    assert_eq!(segments.len(), expected.len());
+    for (a, b) in segments.iter().zip(&expected) {
+        assert_eq!((a.start, a.end), (b.start, b.end));
+    }
+    assert_eq!(windowed.processed_samples(), 512 * 40);
+}
+
+#[test]
+fn test_lookback_only_moves_onsets_earlier() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::from_shape_fn(16000 * 2, |i| {
+        let t = i as f32 / 16000.0;
+        if (0.5..1.2).contains(&t) {
+            0.5 * (t * 300.0 * std::f32::consts::TAU).sin()
+        } else {
+            0.0
+        }
+    });
+
+    let mut plain = vad(&temp_dir);
+    let expected = plain.process_appended(&audio.view()).unwrap();
+
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let config = silero_vad_rs::VadConfig { lookback_samples: 256, ..plain.config().clone() };
+    let mut lookback = VADIterator::from_config(model, config).unwrap();
+    let segments = lookback.process_appended(&audio.view()).unwrap();
+
+    assert_eq!(segments.len(), expected.len());
+    for (a, b) in segments.iter().zip(&expected) {
+        assert!(a.start <= b.start && b.start - a.start <= 256.0 / 16000.0 + 1e-6);
+        assert_eq!(a.end, b.end);
+    }
+    // Probing the boundary leaves the carried model context untouched
+    assert_eq!(lookback.model().context(), plain.model().context());
+}
+
+#[test]
+fn test_continuous_file_pieces_match_whole() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::from_shape_fn(16000 * 4, |i| {
+        let t = i as f32 / 16000.0;
+        if (0.5..1.2).contains(&t) || (2.0..3.1).contains(&t) {
+            0.5 * (t * 300.0 * std::f32::consts::TAU).sin()
+        } else {
+            0.0
+        }
+    });
+
+    let mut whole = vad(&temp_dir).with_continuous_file(true);
+    let expected = whole.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0).unwrap();
+
+    let mut pieces = vad(&temp_dir).with_continuous_file(true);
+    let mut segments = Vec::new();
+    for piece in audio.as_slice().unwrap().chunks(10007) {
+        let piece = ArrayView1::from(piece);
+        segments.extend(pieces.get_speech_timestamps(&piece, 0, f32::INFINITY, 0, 0).unwrap());
+    }
+
+    let bounds = |v: &[silero_vad_rs::SpeechTimestamps]| {
+        v.iter().map(|ts| (ts.start, ts.end)).collect::<Vec<_>>()
+    };
+    assert_eq!(bounds(&segments), bounds(&expected));
+    assert_eq!(pieces.model().context(), whole.model().context());
+
+    // A reset starts a new stream at zero
+    pieces.reset();
+    let again = pieces.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0).unwrap();
+    assert_eq!(bounds(&again), bounds(&expected));
+}
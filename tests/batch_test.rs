@@ -0,0 +1,140 @@
+use ndarray::{s, Array1, Array2};
+use silero_vad_rs::{Error, SileroVAD, VADIterator};
+use tempfile::TempDir;
+
+#[test]
+fn test_batched_detection_processes_partial_batch() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+
+    // 10 windows with a batch size of 4 leaves a trailing batch of 2
+    let audio = Array1::from_shape_fn(512 * 10, |i| 0.1 * (i as f32 / 10.0).sin());
+    vad.get_speech_timestamps_batched(&audio.view(), 4, 250, f32::INFINITY)
+        .unwrap();
+
+    assert_eq!(vad.processed_samples(), 512 * 10);
+}
+
+#[test]
+fn test_batched_detection_matches_window_by_window_detection() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("silero_vad.onnx");
+
+    // Tone bursts separated by silence, with a trailing partial window
+    let audio = Array1::from_shape_fn(512 * 90 + 100, |i| {
+        if (i / 8000) % 2 == 0 {
+            0.5 * (i as f32 * 0.07).sin() * (i as f32 * 0.003).sin()
+        } else {
+            0.0
+        }
+    });
+    let bounds = |timestamps: Vec<silero_vad_rs::SpeechTimestamps>| {
+        timestamps
+            .iter()
+            .map(|ts| (ts.start_sample, ts.end_sample))
+            .collect::<Vec<_>>()
+    };
+
+    let model = SileroVAD::new(&model_path).unwrap();
+    let mut streamed = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+    let expected = bounds(
+        streamed
+            .get_speech_timestamps(&audio.view(), 250, f32::INFINITY, 100, 30)
+            .unwrap(),
+    );
+
+    for batch_size in [1, 4, 7, 32] {
+        let model = SileroVAD::new(&model_path).unwrap();
+        let mut batched = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+        let timestamps = batched
+            .get_speech_timestamps_batched(&audio.view(), batch_size, 250, f32::INFINITY)
+            .unwrap();
+        assert_eq!(bounds(timestamps), expected, "batch size {}", batch_size);
+        assert_eq!(batched.processed_samples(), streamed.processed_samples());
+    }
+}
+
+#[test]
+fn test_classify_batch_thresholds_each_row_without_segmenting() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("silero_vad.onnx");
+    // Tones alternate with silent rows
+    let batch = Array2::from_shape_fn((4, 512), |(r, c)| {
+        if r % 2 == 0 {
+            ((r * 512 + c) as f32 / 10.0).sin() * 0.5
+        } else {
+            0.0
+        }
+    });
+
+    let mut reference = SileroVAD::new(&model_path).unwrap();
+    let probs = reference.process_batch_classes(&batch, 16000).unwrap();
+
+    let model = SileroVAD::new(&model_path).unwrap();
+    let mut vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+    let decisions = vad.classify_batch(&batch).unwrap();
+
+    let expected: Vec<bool> = probs.column(0).iter().map(|&p| p >= 0.5).collect();
+    assert_eq!(decisions, expected);
+    // No segment state or timeline is touched
+    assert_eq!(vad.processed_samples(), 0);
+    assert!(vad.flush().is_none());
+
+    // Every row is speech at a zero threshold
+    let model = SileroVAD::new(&model_path).unwrap();
+    let mut permissive = VADIterator::new(model, 0.0, 16000, 100, 30).unwrap();
+    assert_eq!(permissive.classify_batch(&batch).unwrap(), vec![true; 4]);
+}
+
+#[test]
+fn test_validate_batch_reports_shapes() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+
+    assert!(model.validate_batch(&Array2::zeros((4, 512)), 16000).is_ok());
+
+    match model.validate_batch(&Array2::zeros((512, 4)), 16000) {
+        Err(Error::InvalidInput(message)) => {
+            assert!(message.contains("[512, 4]"));
+            assert!(message.contains("[batch_size, 512]"));
+            assert!(message.contains("transposed"));
+        }
+        _ => panic!("expected a shape error for a transposed batch"),
+    }
+}
+
+#[test]
+fn test_batch_context_is_carried_per_row() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("silero_vad.onnx");
+    let mut batched = SileroVAD::new(&model_path).unwrap();
+
+    // Each row of consecutive batches belongs to its own stream
+    let streams = 3;
+    let batches: Vec<Array2<f32>> = (0..4)
+        .map(|b| {
+            Array2::from_shape_fn((streams, 512), |(r, c)| {
+                ((b * 512 + c) as f32 / (50.0 + 25.0 * r as f32)).sin() * 0.1
+            })
+        })
+        .collect();
+    let batched_probs: Vec<Array2<f32>> = batches
+        .iter()
+        .map(|batch| batched.process_batch_classes(batch, 16000).unwrap())
+        .collect();
+
+    for row in 0..streams {
+        // Stream the same row window by window without resetting in between
+        let mut streamed = SileroVAD::new(&model_path).unwrap();
+        for (batch, probs) in batches.iter().zip(&batched_probs) {
+            let window = batch.row(row);
+            let streamed_probs = streamed.process_chunk_classes(&window, 16000).unwrap();
+            for (streamed_prob, batched_prob) in streamed_probs.iter().zip(probs.row(row)) {
+                assert!((streamed_prob - batched_prob).abs() < 1e-5);
+            }
+            assert_eq!(streamed.context().row(0), window.slice(s![448..]));
+        }
+        assert_eq!(streamed.context().row(0), batched.context().row(row));
+    }
+}
@@ -0,0 +1,150 @@
+use silero_vad_rs::{segment_probabilities, VadConfig};
+
+#[test]
+fn test_vad_config_serde_round_trip() {
+    let config = VadConfig {
+        threshold: 0.65,
+        min_silence_duration_ms: 250,
+        speech_pad_ms: 45,
+        ..Default::default()
+    };
+
+    let json = serde_json::to_string(&config).unwrap();
+    let restored: VadConfig = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(config, restored);
+}
+
+#[test]
+fn test_fill_gaps_bridges_short_dropouts() {
+    let config = VadConfig {
+        gap_fill_windows: 2,
+        ..Default::default()
+    };
+    let mut probs = vec![0.1, 0.9, 0.2, 0.8, 0.1, 0.1, 0.1, 0.7, 0.3];
+    config.fill_gaps(&mut probs);
+
+    assert_eq!(probs, vec![0.1, 0.9, 0.8, 0.8, 0.1, 0.1, 0.1, 0.7, 0.3]);
+}
+
+#[test]
+fn test_fill_gaps_disabled_by_default() {
+    let mut probs = vec![0.9, 0.2, 0.9];
+    VadConfig::default().fill_gaps(&mut probs);
+
+    assert_eq!(probs, vec![0.9, 0.2, 0.9]);
+}
+
+#[test]
+fn test_window_from_ms() {
+    assert_eq!(silero_vad_rs::window_from_ms(32, 16000).unwrap(), 512);
+    assert_eq!(silero_vad_rs::window_from_ms(32, 8000).unwrap(), 256);
+    assert!(silero_vad_rs::window_from_ms(30, 16000).is_err());
+}
+
+#[test]
+fn test_sample_limits_default_to_unset() {
+    let config: VadConfig = serde_json::from_str(r#"{"min_speech_samples": 4000}"#).unwrap();
+
+    assert_eq!(config.min_speech_samples, Some(4000));
+    assert_eq!(config.max_speech_samples, None);
+    assert_eq!(config.sampling_rate, 16000);
+}
+
+#[test]
+fn test_config_for_wav_uses_file_rate() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("tone.wav");
+    let audio = ndarray::Array1::from_elem(1600, 0.1f32);
+
+    silero_vad_rs::utils::save_audio(&path, &audio, 16000).unwrap();
+    assert_eq!(VadConfig::for_wav(&path).unwrap().sampling_rate, 16000);
+
+    silero_vad_rs::utils::save_audio(&path, &audio, 44100).unwrap();
+    assert!(VadConfig::for_wav(&path).is_err());
+}
+
+#[test]
+fn test_command_mode_detects_short_burst() {
+    // A ~150ms word (5 windows of 32ms) at moderate confidence
+    let mut probs = vec![0.05; 5];
+    probs.extend_from_slice(&[0.45; 5]);
+    probs.extend_from_slice(&[0.05; 10]);
+
+    assert!(segment_probabilities(&probs, &VadConfig::default()).is_empty());
+
+    let config = VadConfig::command_mode(16000);
+    let segments = segment_probabilities(&probs, &config);
+    assert_eq!(segments.len(), 1);
+    assert!((segments[0].start - 0.16).abs() < 1e-6);
+    let samples = ((segments[0].end - segments[0].start) * 16000.0).round() as usize;
+    assert!(samples >= config.min_speech_samples.unwrap());
+}
+
+#[test]
+fn test_rate_threshold_selected_by_sampling_rate() {
+    let mut config = VadConfig::default();
+    config.rate_thresholds.insert(8000, 0.3);
+
+    assert_eq!(config.effective_threshold(), 0.5);
+    let telephony = VadConfig { sampling_rate: 8000, ..config.clone() };
+    assert_eq!(telephony.effective_threshold(), 0.3);
+
+    let mut probs = vec![0.4; 6];
+    probs.extend_from_slice(&[0.05; 10]);
+    assert!(segment_probabilities(&probs, &config).is_empty());
+    config.rate_thresholds.insert(16000, 0.35);
+    assert_eq!(segment_probabilities(&probs, &config).len(), 1);
+}
+
+#[test]
+fn test_rate_thresholds_serde_round_trip() {
+    let mut config = VadConfig::default();
+    config.rate_thresholds.insert(8000, 0.3);
+
+    let json = serde_json::to_string(&config).unwrap();
+    let restored: VadConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, config);
+}
+
+#[test]
+fn test_emission_latency() {
+    let config = VadConfig { min_silence_duration_ms: 100, ..Default::default() };
+    // One window to finish the last speech window plus four windows of silence
+    assert_eq!(config.emission_latency_ms(), 160);
+
+    let held = VadConfig { hangover_ms: 50, ..config.clone() };
+    assert_eq!(held.emission_latency_ms(), 224);
+
+    let turns = VadConfig {
+        min_silence_duration_ms: 1000,
+        turn_silence_ms: 300,
+        ..config.clone()
+    };
+    assert_eq!(turns.emission_latency_ms(), 352);
+
+    // The segment really is emitted that late after speech ending mid-window
+    let mut probs = vec![0.9; 3];
+    probs.extend_from_slice(&[0.1; 10]);
+    let emitted_at = (1..=probs.len())
+        .find(|&n| !segment_probabilities(&probs[..n], &config).is_empty())
+        .unwrap();
+    assert_eq!(emitted_at * 32 - 3 * 32, 128);
+    assert!(128 <= config.emission_latency_ms());
+}
+
+#[test]
+fn test_threshold_boundary_inclusive_and_exclusive() {
+    let mut probs = vec![0.5; 4];
+    probs.extend_from_slice(&[0.1; 10]);
+
+    let inclusive = VadConfig::default();
+    assert!(inclusive.inclusive_threshold);
+    assert_eq!(segment_probabilities(&probs, &inclusive).len(), 1);
+
+    let exclusive = VadConfig { inclusive_threshold: false, ..Default::default() };
+    assert!(segment_probabilities(&probs, &exclusive).is_empty());
+
+    let legacy: VadConfig = serde_json::from_str(r#"{"threshold": 0.5}"#).unwrap();
+    assert!(legacy.inclusive_threshold);
+}
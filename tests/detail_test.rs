@@ -0,0 +1,163 @@
+use ndarray::Array1;
+use silero_vad_rs::{SileroVAD, VADIterator, VadConfig};
+use tempfile::TempDir;
+
+#[test]
+fn test_detect_detailed_reports_every_stage() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+
+    let audio = Array1::from_shape_fn(16000 * 2 + 100, |i| {
+        let t = i as f32 / 16000.0;
+        if (0.5..1.5).contains(&t) {
+            0.5 * (t * 220.0 * std::f32::consts::TAU).sin()
+        } else {
+            0.0
+        }
+    });
+    let detail = vad.detect_detailed(&audio.view(), 16000, 0, f32::INFINITY).unwrap();
+
+    assert_eq!(detail.probabilities.len(), audio.len() / 512);
+    assert_eq!(detail.triggers.len(), detail.probabilities.len());
+    let triggered = detail.triggers.iter().filter(|&&t| t).count() as f32 * 512.0 / 16000.0;
+    let initial: f32 = detail.initial_segments.iter().map(|ts| ts.end - ts.start).sum();
+    assert!((initial - triggered).abs() < 1e-4);
+    // Post-processing only ever widens or merges the initial runs
+    for ts in &detail.final_segments {
+        assert!(detail.initial_segments.iter().any(|i| i.start >= ts.start && i.end <= ts.end));
+    }
+}
+
+#[test]
+fn test_detect_detailed_matches_offline_detection() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::from_shape_fn(16000 * 3, |i| {
+        let t = i as f32 / 16000.0;
+        if (0.5..1.0).contains(&t) || (1.5..2.5).contains(&t) {
+            0.5 * (t * 220.0 * std::f32::consts::TAU).sin()
+        } else {
+            0.0
+        }
+    });
+
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+    vad.process_chunk(&audio.slice(ndarray::s![8000..8512])).unwrap();
+    let context = vad.model().context().to_owned();
+    let processed = vad.processed_samples();
+
+    let detail = vad.detect_detailed(&audio.view(), 16000, 600, f32::INFINITY).unwrap();
+    assert_eq!(vad.model().context(), context);
+    assert_eq!(vad.processed_samples(), processed);
+
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut offline = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+    // Same model context, but a fresh timeline
+    offline.process_chunk(&audio.slice(ndarray::s![8000..8512])).unwrap();
+    offline.reset_segments();
+    let expected = offline.get_speech_timestamps(&audio.view(), 600, f32::INFINITY, 100, 30).unwrap();
+    assert_eq!(detail.final_segments.len(), expected.len());
+    for (detailed, expected) in detail.final_segments.iter().zip(&expected) {
+        assert_eq!((detailed.start, detailed.end), (expected.start, expected.end));
+    }
+
+    assert!(vad.detect_detailed(&audio.view(), 8000, 0, f32::INFINITY).is_err());
+}
+
+#[test]
+fn test_feature_matrix_columns() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+
+    let audio = Array1::from_shape_fn(512 * 6 + 10, |i| if i % 2 == 0 { 0.25 } else { -0.25 });
+    let features = vad.feature_matrix(&audio.view(), 16000).unwrap();
+
+    assert_eq!(features.dim(), (6, 3));
+    for (i, row) in features.rows().into_iter().enumerate() {
+        assert!((row[0] - i as f32 * 0.032).abs() < 1e-5);
+        assert!((0.0..=1.0).contains(&row[1]));
+        assert!((row[2] - 0.25).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_time_scale_applies_to_every_reported_time() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::from_shape_fn(512 * 6, |i| if i % 2 == 0 { 0.25 } else { -0.25 });
+
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+    assert!(vad.with_time_scale(0.0).is_err());
+
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.0, 16000, 100, 30).unwrap().with_time_scale(2.0).unwrap();
+    let features = vad.feature_matrix(&audio.view(), 16000).unwrap();
+    for (i, row) in features.rows().into_iter().enumerate() {
+        assert!((row[0] - i as f32 * 0.064).abs() < 1e-5);
+    }
+
+    vad.reset();
+    let detail = vad.detect_detailed(&audio.view(), 16000, 0, f32::INFINITY).unwrap();
+    // Every window is speech at a zero threshold
+    assert_eq!(detail.initial_segments.len(), 1);
+    assert!((detail.initial_segments[0].end - 6.0 * 0.064).abs() < 1e-5);
+}
+
+#[test]
+fn test_detect_with_audio_slices_by_sample_position() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::from_shape_fn(512 * 6 + 100, |i| (i as f32 * 0.1).sin() * 0.25);
+
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.0, 16000, 100, 30).unwrap().with_time_scale(0.7).unwrap();
+    let segments = vad.detect_with_audio(&audio.view(), 16000).unwrap();
+
+    assert!(!segments.is_empty());
+    for segment in segments {
+        let ts = &segment.timestamps;
+        assert_eq!(segment.audio, audio.slice(ndarray::s![ts.start_sample..ts.end_sample]));
+    }
+}
+
+#[test]
+fn test_suggest_parameters_keeps_the_model_context() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+    let audio = Array1::from_shape_fn(16000 * 2, |i| 0.3 * (i as f32 * 0.07).sin());
+
+    let suggested = vad.suggest_parameters(&audio.view(), 16000).unwrap();
+    assert!((0.2..=0.8).contains(&suggested.config.threshold));
+    assert_eq!(suggested.config.min_speech_samples, None);
+    assert!(vad.model().context().iter().all(|&v| v == 0.0));
+}
+
+#[test]
+fn test_clipped_onsets_are_found_by_sample_position_when_times_are_scaled() {
+    let temp_dir = TempDir::new().unwrap();
+    // A hard-clipped burst opens the clip, which is speech throughout at a zero threshold
+    let audio = Array1::from_shape_fn(512 * 6, |i| {
+        if i >= 512 {
+            (i as f32 * 0.1).sin() * 0.25
+        } else if i % 2 == 0 {
+            1.0
+        } else {
+            -1.0
+        }
+    });
+    let config = VadConfig { threshold: 0.0, time_scale: 0.7, ..Default::default() };
+
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::from_config(model, config.clone()).unwrap();
+    let kept = vad.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 100, 30).unwrap();
+    assert_eq!(kept.len(), 1);
+    assert_eq!(kept[0].start_sample, 0);
+
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let config = VadConfig { suppress_clipped_onsets: true, ..config };
+    let mut vad = VADIterator::from_config(model, config).unwrap();
+    let kept = vad.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 100, 30).unwrap();
+    assert!(kept.is_empty());
+}
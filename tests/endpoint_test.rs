@@ -0,0 +1,63 @@
+use ndarray::Array1;
+use silero_vad_rs::{SileroVAD, VADIterator};
+use tempfile::TempDir;
+
+fn vad(temp_dir: &TempDir) -> VADIterator {
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    VADIterator::new(model, 0.5, 16000, 100, 30).unwrap()
+}
+
+/// One second of silence, one second of a voiced tone, one second of silence
+fn tone_burst() -> Array1<f32> {
+    Array1::from_shape_fn(16000 * 3, |i| {
+        let t = i as f32 / 16000.0;
+        if (1.0..2.0).contains(&t) {
+            0.5 * (t * 180.0 * std::f32::consts::TAU).sin()
+        } else {
+            0.0
+        }
+    })
+}
+
+#[test]
+fn test_first_speech_time_agrees_with_streaming_onset() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = tone_burst();
+
+    let offline = vad(&temp_dir).first_speech_time(&audio.view(), 16000).unwrap();
+
+    let mut streaming = vad(&temp_dir);
+    let mut onsets = Vec::new();
+    for chunk in audio.exact_chunks(512) {
+        if let Some(time) = streaming.poll_first_speech(&chunk).unwrap() {
+            onsets.push(time);
+        }
+    }
+
+    assert!(onsets.len() <= 1);
+    assert_eq!(onsets.len(), usize::from(offline.is_some()));
+    if let (Some(&streamed), Some(offline)) = (onsets.first(), offline) {
+        assert!((streamed - offline).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_poll_endpoint_returns_finalized_segments() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = tone_burst();
+
+    let mut chunked = vad(&temp_dir);
+    let mut polled = vad(&temp_dir);
+    let mut expected = Vec::new();
+    let mut segments = Vec::new();
+    for chunk in audio.exact_chunks(512) {
+        expected.extend(chunked.process_chunk(&chunk).unwrap());
+        let (_, segment) = polled.poll_endpoint(&chunk).unwrap();
+        segments.extend(segment);
+    }
+
+    assert_eq!(segments.len(), expected.len());
+    for (segment, expected) in segments.iter().zip(&expected) {
+        assert_eq!((segment.start, segment.end), (expected.start, expected.end));
+    }
+}
@@ -0,0 +1,30 @@
+use silero_vad_rs::VadEvent;
+
+#[test]
+fn test_event_json_lines_format() {
+    let mut out = Vec::new();
+    VadEvent::SpeechStart { timestamp: 1.5, probability: Some(0.75) }
+        .write_json_line(&mut out)
+        .unwrap();
+    VadEvent::SpeechEnd { timestamp: 2.25, probability: None }
+        .write_json_line(&mut out)
+        .unwrap();
+
+    let text = String::from_utf8(out).unwrap();
+    assert_eq!(
+        text,
+        "{\"event\":\"speech_start\",\"timestamp\":1.5,\"probability\":0.75}\n\
+         {\"event\":\"speech_end\",\"timestamp\":2.25}\n"
+    );
+}
+
+#[test]
+fn test_event_json_lines_round_trip() {
+    let event = VadEvent::SpeechEnd { timestamp: 3.0, probability: Some(0.5) };
+    let mut out = Vec::new();
+    event.write_json_line(&mut out).unwrap();
+
+    let line = String::from_utf8(out).unwrap();
+    let parsed: VadEvent = serde_json::from_str(line.trim_end()).unwrap();
+    assert_eq!(parsed, event);
+}
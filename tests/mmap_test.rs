@@ -0,0 +1,148 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+use ndarray::Array1;
+use silero_vad_rs::utils::{read_audio_mmap, PrefetchWindows};
+use silero_vad_rs::{SileroVAD, VADIterator};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn spec(channels: u16, bits_per_sample: u16, sample_format: SampleFormat) -> WavSpec {
+    WavSpec { channels, sample_rate: 16000, bits_per_sample, sample_format }
+}
+
+#[test]
+fn test_mmap_pcm16_stereo_downmix() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("stereo.wav");
+    let mut writer = WavWriter::create(&path, spec(2, 16, SampleFormat::Int)).unwrap();
+    for i in 0..1100i16 {
+        writer.write_sample(i * 8).unwrap();
+        writer.write_sample(i * 16).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let wav = read_audio_mmap(&path).unwrap();
+    assert_eq!(wav.sampling_rate(), 16000);
+    assert_eq!(wav.channels(), 2);
+    assert_eq!(wav.len(), 1100);
+
+    let windows: Vec<_> = wav.windows(512).collect();
+    assert_eq!(windows.len(), 2);
+    assert!((windows[1][0] - (512.0 * 12.0) / 32768.0).abs() < 1e-6);
+    assert_eq!(wav.read(1090, 512).len(), 10);
+}
+
+#[test]
+fn test_mmap_float_mono() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("float.wav");
+    let mut writer =
+        WavWriter::create(&path, spec(1, 32, SampleFormat::Float)).unwrap();
+    for i in 0..1024 {
+        writer.write_sample(i as f32 / 1024.0).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let wav = read_audio_mmap(&path).unwrap();
+    let window = wav.read(256, 4);
+    assert_eq!(window.to_vec(), vec![0.25, 257.0 / 1024.0, 258.0 / 1024.0, 259.0 / 1024.0]);
+}
+
+#[test]
+fn test_mmap_rejects_unsupported_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("pcm24.wav");
+    let mut writer = WavWriter::create(&path, spec(1, 24, SampleFormat::Int)).unwrap();
+    writer.write_sample(0i32).unwrap();
+    writer.finalize().unwrap();
+
+    assert!(read_audio_mmap(&path).is_err());
+    std::fs::write(&path, b"not a wav file").unwrap();
+    assert!(read_audio_mmap(&path).is_err());
+}
+
+#[test]
+fn test_prefetched_windows_match_direct_reads() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("long.wav");
+    let mut writer = WavWriter::create(&path, spec(1, 16, SampleFormat::Int)).unwrap();
+    for i in 0..512 * 20 + 100 {
+        writer.write_sample((i % 2000) as i16).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let expected: Vec<_> = read_audio_mmap(&path).unwrap().windows(512).collect();
+    let prefetched: Vec<_> = read_audio_mmap(&path).unwrap().prefetch_windows(512, 3).collect();
+    assert_eq!(prefetched, expected);
+}
+
+#[test]
+fn test_dropping_prefetch_stops_the_reader() {
+    let produced = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&produced);
+    let source = (0..1000).map(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Array1::<f32>::zeros(512)
+    });
+
+    let mut windows = PrefetchWindows::new(source, 1);
+    assert!(windows.next().is_some());
+    drop(windows);
+
+    // Drop joins the reader: one window consumed, one queued and one that
+    // failed to send, and nothing more is read afterwards
+    let after_drop = produced.load(Ordering::SeqCst);
+    assert!(after_drop <= 3, "reader produced {} windows", after_drop);
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    assert_eq!(produced.load(Ordering::SeqCst), after_drop);
+}
+
+#[test]
+#[should_panic(expected = "disk read failed")]
+fn test_prefetch_propagates_a_source_panic() {
+    let source = (0..10).map(|i| {
+        if i == 3 {
+            panic!("disk read failed");
+        }
+        Array1::<f32>::zeros(512)
+    });
+
+    // The windows read before the failure are delivered, then the panic
+    let mut windows = PrefetchWindows::new(source, 2);
+    for _ in 0..3 {
+        assert!(windows.next().is_some());
+    }
+    windows.next();
+}
+
+#[test]
+fn test_stream_wav_matches_in_memory_detection() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("speech.wav");
+    let mut writer = WavWriter::create(&path, spec(1, 16, SampleFormat::Int)).unwrap();
+    for i in 0..512 * 60 {
+        // A 440 Hz tone in the middle third, silence around it
+        let tone = (20..40).contains(&(i / 512));
+        let sample = if tone { (i as f32 * 0.1728).sin() * 8000.0 } else { 0.0 };
+        writer.write_sample(sample as i16).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let vad = || {
+        let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+        VADIterator::new(model, 0.5, 16000, 100, 30).unwrap()
+    };
+
+    let audio = read_audio_mmap(&path).unwrap().read(0, 512 * 60);
+    let view = audio.view();
+    let bounds = |segments: Vec<silero_vad_rs::SpeechTimestamps>| {
+        segments.iter().map(|ts| (ts.start, ts.end)).collect::<Vec<_>>()
+    };
+    let expected = vad().segments_iter(&view, 16000).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(bounds(vad().stream_wav(&path, 4).unwrap()), bounds(expected));
+
+    // A file at another rate is rejected before any inference
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut narrowband = VADIterator::new(model, 0.5, 8000, 100, 30).unwrap();
+    assert!(narrowband.stream_wav(&path, 4).is_err());
+}
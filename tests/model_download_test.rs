@@ -1,6 +1,8 @@
 use std::fs;
 use tempfile::TempDir;
 use ort::session::{Session, builder::GraphOptimizationLevel};
+use silero_vad_rs::{download_model, Error, ModelConfig, Provider, SileroVAD};
+use std::time::Duration;
 
 const MODEL_URL: &str = "https://models.silero.ai/models/en/en_v6_xlarge.onnx";
 
@@ -35,4 +37,239 @@ fn test_model_auto_download() {
     assert!(!model_files.is_empty(), "Model should be downloaded to the cache directory");
     
     // Clean up is handled automatically by TempDir when it goes out of scope
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_download_failure_is_retried_and_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("missing.onnx");
+    let config = ModelConfig {
+        model_url: "http://127.0.0.1:9/silero_vad.onnx".to_string(),
+        download_attempts: 2,
+        retry_base_delay: Duration::from_millis(10),
+        ..Default::default()
+    };
+
+    let result = SileroVAD::with_config(&model_path, config);
+
+    assert!(matches!(result, Err(Error::Download(_))));
+}
+
+#[test]
+fn test_mismatched_thread_affinity_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = ModelConfig {
+        model_url: "http://127.0.0.1:9/silero_vad.onnx".to_string(),
+        intra_threads: 3,
+        thread_affinity: vec![vec![2]],
+        ..Default::default()
+    };
+
+    let result = SileroVAD::with_config(&temp_dir.path().join("silero_vad.onnx"), config);
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
+
+/// Encode a length-delimited protobuf field
+fn proto_bytes(field: u32, payload: &[u8]) -> Vec<u8> {
+    let mut out = proto_varint_raw(u64::from(field << 3 | 2));
+    out.extend(proto_varint_raw(payload.len() as u64));
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Encode a varint protobuf field
+fn proto_varint(field: u32, value: u64) -> Vec<u8> {
+    let mut out = proto_varint_raw(u64::from(field << 3));
+    out.extend(proto_varint_raw(value));
+    out
+}
+
+fn proto_varint_raw(mut value: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+    out
+}
+
+/// A valid ONNX model that copies a float vector `x` to `y`
+fn identity_model() -> Vec<u8> {
+    let value_info = |name: &str| {
+        let dim = proto_bytes(1, &proto_varint(1, 4));
+        let shape = proto_bytes(2, &dim);
+        let tensor = [proto_varint(1, 1), shape].concat();
+        let type_proto = proto_bytes(1, &tensor);
+        [proto_bytes(1, name.as_bytes()), proto_bytes(2, &type_proto)].concat()
+    };
+    let node = [
+        proto_bytes(1, b"x"),
+        proto_bytes(2, b"y"),
+        proto_bytes(4, b"Identity"),
+    ]
+    .concat();
+    let graph = [
+        proto_bytes(1, &node),
+        proto_bytes(2, b"identity"),
+        proto_bytes(11, &value_info("x")),
+        proto_bytes(12, &value_info("y")),
+    ]
+    .concat();
+    [
+        proto_varint(1, 7),
+        proto_bytes(7, &graph),
+        proto_bytes(8, &proto_varint(2, 13)),
+    ]
+    .concat()
+}
+
+#[test]
+fn test_non_vad_model_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("identity.onnx");
+    fs::write(&path, identity_model()).unwrap();
+
+    match SileroVAD::new(&path) {
+        Err(Error::ModelLoad(message)) => {
+            assert_eq!(message.matches("does not appear to be a Silero VAD model").count(), 1);
+            assert!(message.contains("identity.onnx"));
+            assert!(message.contains("no audio input named \"input\""));
+        }
+        Err(e) => panic!("expected a model load error, got {}", e),
+        Ok(_) => panic!("an identity model was accepted as a Silero VAD model"),
+    }
+}
+
+/// Serve `body` over HTTP, dropping the first connection halfway through
+fn serve_flaky(body: Vec<u8>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/model.onnx", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut ranges = Vec::new();
+        for connection in 0..2 {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut offset = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some(range) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                    ranges.push(range.trim().to_string());
+                    offset = range.trim().trim_end_matches('-').parse().unwrap();
+                }
+            }
+
+            if connection == 0 {
+                let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&body[..body.len() / 2]).unwrap();
+            } else {
+                let head = format!(
+                    "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\n\r\n",
+                    body.len() - offset,
+                    offset,
+                    body.len() - 1,
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&body[offset..]).unwrap();
+            }
+        }
+        ranges
+    });
+    (url, handle)
+}
+
+#[test]
+fn test_interrupted_download_is_resumed() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("model.onnx");
+    let body: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    let (url, server) = serve_flaky(body.clone());
+
+    let config = ModelConfig {
+        download_attempts: 2,
+        retry_base_delay: Duration::from_millis(10),
+        ..Default::default()
+    };
+    download_model(&url, &model_path, &config).unwrap();
+
+    assert_eq!(fs::read(&model_path).unwrap(), body);
+    assert!(!temp_dir.path().join("model.onnx.part").exists());
+    // The second attempt only asked for the missing half
+    assert_eq!(server.join().unwrap(), vec![format!("{}-", body.len() / 2)]);
+}
+
+/// Serve `body` over HTTP without announcing its size, once per connection
+fn serve_unsized(body: Vec<u8>, connections: usize) -> String {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/model.onnx", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for _ in 0..connections {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            stream.write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n").unwrap();
+            stream.write_all(&body).unwrap();
+        }
+    });
+    url
+}
+
+/// SHA-256 of the bytes `(0..100_000).map(|i| i % 251)`
+const BODY_SHA256: &str = "cd2df694e424bc7968cc37f47751019e5ca0cd1bdf2e479ea537c3a1c32ee1aa";
+
+#[test]
+fn test_unsized_download_is_verified_by_checksum() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("model.onnx");
+    let body: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    let config = ModelConfig {
+        download_attempts: 2,
+        retry_base_delay: Duration::from_millis(10),
+        model_sha256: Some(BODY_SHA256.to_string()),
+        ..Default::default()
+    };
+
+    // A body cut short without a Content-Length is not put in place
+    let url = serve_unsized(body[..body.len() / 2].to_vec(), 2);
+    let result = download_model(&url, &model_path, &config);
+    assert!(matches!(result, Err(Error::Download(message)) if message.contains("checksum")));
+    assert!(!model_path.exists());
+    assert!(!temp_dir.path().join("model.onnx.part").exists());
+
+    let url = serve_unsized(body.clone(), 1);
+    download_model(&url, &model_path, &config).unwrap();
+    assert_eq!(fs::read(&model_path).unwrap(), body);
+}
+
+#[test]
+fn test_cpu_must_be_last_provider() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = ModelConfig {
+        model_url: "http://127.0.0.1:9/silero_vad.onnx".to_string(),
+        execution_providers: vec![Provider::Cpu, Provider::DirectML],
+        ..Default::default()
+    };
+
+    let result = SileroVAD::with_config(&temp_dir.path().join("silero_vad.onnx"), config);
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
+
+#[test]
+fn test_loaded_model_passes_signature_validation() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    assert!(model.validate().is_ok());
+}
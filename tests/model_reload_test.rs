@@ -0,0 +1,57 @@
+use ndarray::Array1;
+use silero_vad_rs::{Language, Provider, SileroVAD};
+use tempfile::TempDir;
+
+#[test]
+fn test_reload_resets_context() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+
+    let chunk = Array1::from_shape_fn(512, |i| 0.1 * (i as f32 / 10.0).sin());
+    model.process_chunk(&chunk.view(), 16000).unwrap();
+    assert!(model.context().iter().any(|&v| v != 0.0));
+
+    model.reload(Language::English).unwrap();
+    assert!(model.context().iter().all(|&v| v == 0.0));
+}
+
+#[test]
+fn test_reload_keeps_the_downloaded_model_next_to_the_current_one() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+
+    model.reload(Language::German).unwrap();
+    assert!(temp_dir.path().join("de_v1.onnx").exists());
+
+    // A model without an ONNX release is rejected before any download
+    assert!(model.reload(Language::Russian).is_err());
+}
+
+#[test]
+fn test_force_cpu_switches_provider() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+
+    model.force_cpu().unwrap();
+    assert_eq!(model.active_provider(), Provider::Cpu);
+
+    let chunk = Array1::from_shape_fn(512, |i| 0.1 * (i as f32 / 10.0).sin());
+    let probs = model.process_chunk(&chunk.view(), 16000).unwrap();
+    assert!(probs.iter().all(|p| (0.0..=1.0).contains(p)));
+}
+
+#[test]
+fn test_force_cpu_reloads_from_the_model_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("silero_vad.onnx");
+    let mut model = SileroVAD::new(&model_path).unwrap();
+
+    // The session is rebuilt from the file, never fetched again
+    std::fs::remove_file(&model_path).unwrap();
+    assert!(model.force_cpu().is_err());
+    assert!(!model_path.exists());
+
+    // The current session is kept
+    let chunk = Array1::from_shape_fn(512, |i| 0.1 * (i as f32 / 10.0).sin());
+    assert!(model.process_chunk(&chunk.view(), 16000).is_ok());
+}
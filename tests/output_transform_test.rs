@@ -0,0 +1,17 @@
+use silero_vad_rs::{ModelConfig, OutputTransform};
+
+#[test]
+fn test_identity_is_default() {
+    assert_eq!(ModelConfig::default().output_transform, OutputTransform::Identity);
+    assert_eq!(OutputTransform::Identity.apply(0.73), 0.73);
+}
+
+#[test]
+fn test_sigmoid_maps_logits_to_probabilities() {
+    let sigmoid = OutputTransform::Sigmoid;
+
+    assert!((sigmoid.apply(0.0) - 0.5).abs() < 1e-6);
+    assert!((sigmoid.apply(2.0) - 0.880797).abs() < 1e-5);
+    assert!((sigmoid.apply(-2.0) - 0.119203).abs() < 1e-5);
+    assert!(sigmoid.apply(50.0) <= 1.0 && sigmoid.apply(-50.0) >= 0.0);
+}
@@ -0,0 +1,249 @@
+use ndarray::Array1;
+use silero_vad_rs::{SileroVAD, VADIterator, VadConfig};
+use tempfile::TempDir;
+
+fn vad() -> (TempDir, VADIterator) {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    (temp_dir, VADIterator::new(model, 0.5, 16000, 100, 30).unwrap())
+}
+
+fn chunk() -> Array1<f32> {
+    Array1::from_shape_fn(512, |i| 0.1 * (i as f32 / 10.0).sin())
+}
+
+/// Half-second harmonic bursts separated by half a second of silence
+fn bursts() -> Array1<f32> {
+    Array1::from_shape_fn(16000 * 4, |i| {
+        if (i / 8000) % 2 == 1 {
+            return 0.0;
+        }
+        let t = i as f32 / 16000.0;
+        let envelope = 0.5 + 0.5 * (2.0 * std::f32::consts::PI * 4.0 * t).sin();
+        envelope
+            * (0.4 * (2.0 * std::f32::consts::PI * 150.0 * t).sin()
+                + 0.2 * (2.0 * std::f32::consts::PI * 300.0 * t).sin()
+                + 0.1 * (2.0 * std::f32::consts::PI * 450.0 * t).sin())
+    })
+}
+
+#[test]
+fn test_reset_model_keeps_timeline() {
+    let (_dir, mut vad) = vad();
+    vad.process_chunk(&chunk().view()).unwrap();
+
+    vad.reset_model();
+
+    assert!(vad.model().context().iter().all(|&v| v == 0.0));
+    assert_eq!(vad.processed_samples(), 512);
+}
+
+#[test]
+fn test_reset_segments_keeps_model_context() {
+    let (_dir, mut vad) = vad();
+    vad.process_chunk(&chunk().view()).unwrap();
+
+    vad.reset_segments();
+
+    assert!(vad.model().context().iter().any(|&v| v != 0.0));
+    assert_eq!(vad.processed_samples(), 512);
+}
+
+#[test]
+fn test_reset_clears_everything() {
+    let (_dir, mut vad) = vad();
+    vad.process_chunk(&chunk().view()).unwrap();
+
+    vad.reset();
+
+    assert!(vad.model().context().iter().all(|&v| v == 0.0));
+    assert_eq!(vad.processed_samples(), 0);
+}
+
+#[test]
+fn test_process_chunk_at_flags_skipped_chunks() {
+    let (_dir, mut vad) = vad();
+    vad.process_chunk_at(&chunk().view(), 0).unwrap();
+    vad.process_chunk_at(&chunk().view(), 512).unwrap();
+    assert_eq!(vad.discontinuities(), 0);
+
+    // The caller dropped a chunk in between
+    vad.process_chunk_at(&chunk().view(), 1536).unwrap();
+    assert_eq!(vad.discontinuities(), 1);
+
+    vad.reset();
+    assert_eq!(vad.discontinuities(), 0);
+}
+
+#[test]
+fn test_segments_iter_covers_trailing_partial_window() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+
+    let audio = Array1::<f32>::zeros(512 * 3 + 100);
+    let view = audio.view();
+    let segments: Vec<_> = vad
+        .segments_iter(&view, 16000)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert!(segments.is_empty());
+    assert_eq!(vad.processed_samples(), audio.len());
+    assert!(vad.flush().is_none());
+
+    let mut mismatched = vad.segments_iter(&view, 8000);
+    assert!(matches!(mismatched.next(), Some(Err(_))));
+    assert!(mismatched.next().is_none());
+}
+
+#[test]
+fn test_segments_iter_counts_only_real_samples_of_the_tail() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    // Every window is speech at a zero threshold
+    let mut vad = VADIterator::new(model, 0.0, 16000, 100, 30).unwrap();
+
+    let audio = Array1::from_shape_fn(512 * 3 + 100, |i| (i as f32 * 0.1).sin() * 0.25);
+    let view = audio.view();
+    let segments: Vec<_> = vad
+        .segments_iter(&view, 16000)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].end_sample, audio.len());
+    let speech = vad.running_stats().speech_duration_s;
+    assert!((speech - audio.len() as f32 / 16000.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_probability_history_is_bounded_and_cleared_by_reset() {
+    let (_dir, detector) = vad();
+    let mut history = detector.with_probability_history(3);
+    for _ in 0..5 {
+        history.process_chunk(&chunk().view()).unwrap();
+    }
+
+    let recent = history.recent_probabilities();
+    assert_eq!(recent.len(), 3);
+    assert!(recent.iter().all(|p| (0.0..=1.0).contains(p)));
+
+    history.reset();
+    assert!(history.recent_probabilities().is_empty());
+
+    let (_other_dir, mut disabled) = vad();
+    disabled.process_chunk(&chunk().view()).unwrap();
+    assert!(disabled.recent_probabilities().is_empty());
+}
+
+#[test]
+fn test_gap_fill_scores_windows_like_the_standard_path() {
+    let audio = bursts();
+    let windows = audio.len() / 512;
+
+    let (_dir, standard) = vad();
+    let mut standard = standard.with_probability_history(windows);
+    standard.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0).unwrap();
+    let mut expected = standard.recent_probabilities().to_vec();
+
+    // The model is reset after each emitted segment on both paths, so the
+    // gap-filled curve is exactly the filled standard one
+    let (_gap_dir, gap_filled) = vad();
+    let mut gap_filled = gap_filled.with_probability_history(windows).with_gap_fill(1);
+    gap_filled.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0).unwrap();
+    gap_filled.config().fill_gaps(&mut expected);
+
+    assert_eq!(gap_filled.recent_probabilities(), expected.as_slice());
+}
+
+#[test]
+fn test_half_window_aligned_pass_scores_like_the_standard_path() {
+    let audio = bursts();
+    let windows = audio.len() / 512;
+
+    let (_dir, standard) = vad();
+    let mut standard = standard.with_probability_history(windows);
+    standard.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0).unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let config = VadConfig {
+        min_silence_duration_ms: 100,
+        speech_pad_ms: 30,
+        half_window_refinement: true,
+        probability_history: windows * 2,
+        ..Default::default()
+    };
+    let mut refined = VADIterator::from_config(model, config).unwrap();
+    refined.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0).unwrap();
+
+    // The history interleaves the aligned and offset passes
+    let aligned: Vec<f32> = refined.recent_probabilities().iter().step_by(2).copied().collect();
+    assert_eq!(aligned, standard.recent_probabilities());
+}
+
+#[test]
+fn test_bidirectional_pass_is_aligned_with_the_reversed_audio() {
+    // A trailing partial window, which neither pass scores
+    let mut samples = bursts().to_vec();
+    samples.extend(std::iter::repeat(0.0).take(100));
+    let audio = Array1::from(samples);
+    let windows = audio.len() / 512;
+
+    // Nothing is emitted within the clip, so neither pass resets the model
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let config = VadConfig {
+        min_silence_duration_ms: 60_000,
+        bidirectional: true,
+        probability_history: windows,
+        ..Default::default()
+    };
+    let mut detector = VADIterator::from_config(model, config).unwrap();
+    detector.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0).unwrap();
+
+    let view = audio.view();
+    let (_forward_dir, mut forward_vad) = vad();
+    let forward: Vec<f32> = forward_vad.probabilities(&view, 16000).map(|r| r.unwrap().1).collect();
+    let reversed: Array1<f32> = audio.iter().take(windows * 512).rev().copied().collect();
+    let reversed_view = reversed.view();
+    let (_backward_dir, mut backward_vad) = vad();
+    let backward: Vec<f32> =
+        backward_vad.probabilities(&reversed_view, 16000).map(|r| r.unwrap().1).collect();
+    assert_eq!(backward.len(), windows);
+
+    let expected: Vec<f32> =
+        (0..windows).map(|i| (forward[i] + backward[windows - 1 - i]) / 2.0).collect();
+    assert_eq!(detector.recent_probabilities(), expected.as_slice());
+}
+
+#[test]
+fn test_probabilities_pair_each_window_with_its_streaming_probability() {
+    let audio = bursts();
+    let view = audio.view();
+    let (_dir, mut lazy) = vad();
+    let pairs: Vec<(Array1<f32>, f32)> = lazy
+        .probabilities(&view, 16000)
+        .take(5)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    // Only the requested windows are scored, and the timeline is untouched
+    assert_eq!(pairs.len(), 5);
+    assert_eq!(lazy.processed_samples(), 0);
+
+    // The model context carries across windows as in streaming
+    let temp_dir = TempDir::new().unwrap();
+    let mut streamed = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    for (i, (window, prob)) in pairs.iter().enumerate() {
+        let expected = audio.slice(ndarray::s![i * 512..(i + 1) * 512]);
+        assert_eq!(window.view(), expected);
+        assert_eq!(*prob, streamed.process_chunk(&expected, 16000).unwrap()[0]);
+    }
+
+    // A trailing partial window is ignored
+    let short = Array1::<f32>::zeros(512 * 2 + 100);
+    let short_view = short.view();
+    assert_eq!(lazy.probabilities(&short_view, 16000).count(), 2);
+}
@@ -0,0 +1,49 @@
+use ndarray::Array1;
+use silero_vad_rs::utils::save_audio;
+use silero_vad_rs::{SileroVAD, SpeechTimestamps, VADIterator, VadResult};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn segment(start: f32, end: f32, peak_confidence: f32) -> SpeechTimestamps {
+    SpeechTimestamps { peak_confidence, ..SpeechTimestamps::new(start, end) }
+}
+
+#[test]
+fn test_summary_line_format() {
+    let result = VadResult {
+        path: PathBuf::from("calls/first call.wav"),
+        duration_s: 10.0,
+        segments: vec![segment(1.0, 2.5, 0.9), segment(4.0, 5.0, 0.975)],
+    };
+
+    assert_eq!(
+        result.summary_line(),
+        r#"path="calls/first call.wav" duration=10.000s segments=2 speech=2.500s ratio=0.250 peak=0.975"#
+    );
+}
+
+#[test]
+fn test_summary_line_without_speech() {
+    let result = VadResult { path: PathBuf::from("empty.wav"), duration_s: 0.0, segments: vec![] };
+
+    assert_eq!(
+        result.summary_line(),
+        r#"path="empty.wav" duration=0.000s segments=0 speech=0.000s ratio=0.000 peak=0.000"#
+    );
+}
+
+#[test]
+fn test_detect_file_resamples_to_the_configured_rate() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("call.wav");
+    // One second recorded at 32kHz
+    let audio = Array1::from_shape_fn(32000, |i| (i as f32 * 0.05).sin() * 0.25);
+    save_audio(&path, &audio, 32000).unwrap();
+
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+    let result = vad.detect_file(&path).unwrap();
+
+    assert_eq!(result.path, path);
+    assert!((result.duration_s - 1.0).abs() < 1e-3);
+}
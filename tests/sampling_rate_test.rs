@@ -0,0 +1,71 @@
+use ndarray::Array1;
+use silero_vad_rs::model::CONTEXT_SIZE;
+use silero_vad_rs::{window_from_ms, Error, SileroVAD, VADIterator, SUPPORTED_SAMPLING_RATES};
+use tempfile::TempDir;
+
+#[test]
+fn test_unsupported_sampling_rate_fails_at_construction() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+
+    let result = VADIterator::new(model, 0.5, 44100, 100, 30);
+    match result {
+        Err(Error::InvalidInput(message)) => assert!(message.contains("44100")),
+        _ => panic!("expected an invalid input error for 44.1kHz"),
+    }
+}
+
+#[test]
+fn test_rate_change_mid_stream() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut vad = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+    let chunk = Array1::<f32>::zeros(512);
+
+    for _ in 0..4 {
+        vad.process_chunk_with_rate(&chunk.view(), 16000).unwrap();
+    }
+    assert_eq!(vad.processed_samples(), 2048);
+
+    // The model only accepts 16kHz: an 8kHz chunk is rejected and the
+    // stream continues unchanged at 16kHz
+    let telephony = Array1::<f32>::zeros(256);
+    match vad.process_chunk_with_rate(&telephony.view(), 8000) {
+        Err(Error::InvalidInput(message)) => assert!(message.contains("8000")),
+        _ => panic!("expected an invalid input error for 8kHz"),
+    }
+    assert_eq!(vad.config().sampling_rate, 16000);
+    assert_eq!(vad.processed_samples(), 2048);
+
+    vad.process_chunk_with_rate(&chunk.view(), 16000).unwrap();
+    assert_eq!(vad.processed_samples(), 2560);
+
+    // Switching to the current rate keeps the timeline and the settings
+    let config = vad.config().clone();
+    vad.switch_sampling_rate(16000).unwrap();
+    assert_eq!(vad.processed_samples(), 2560);
+    assert_eq!(vad.config(), &config);
+    vad.process_chunk(&chunk.view()).unwrap();
+    assert_eq!(vad.processed_samples(), 3072);
+}
+
+#[test]
+fn test_window_size_agrees_for_every_supported_rate() {
+    let temp_dir = TempDir::new().unwrap();
+    for &sr in SUPPORTED_SAMPLING_RATES {
+        let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+        let mut vad = VADIterator::new(model, 0.5, sr, 100, 30).unwrap();
+
+        // Configuration in time, chunking and chunk validation share one size
+        let window = window_from_ms(32, sr).unwrap();
+        assert!(window_from_ms(31, sr).is_err());
+        assert_eq!(vad.chunk_count(window * 3 + window - 1), 3);
+        assert!(vad.process_chunk(&Array1::zeros(window - 1).view()).is_err());
+        assert!(vad.process_chunk(&Array1::zeros(window + 1).view()).is_err());
+
+        // The model accepts the window together with the carried context
+        assert!(vad.process_chunk(&Array1::zeros(window).view()).is_ok());
+        assert_eq!(vad.processed_samples(), window);
+        assert_eq!(vad.model().context().ncols(), CONTEXT_SIZE);
+    }
+}
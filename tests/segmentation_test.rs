@@ -0,0 +1,457 @@
+use silero_vad_rs::{
+    endpoint_from_probs, events_from_probs, segment_from_bidirectional,
+    segment_from_offset_passes, segment_probabilities, stats_from_probs,
+    suggest_parameters_from_probs, EndpointState, SplitReason, VadConfig, VadEvent,
+};
+
+fn config() -> VadConfig {
+    VadConfig {
+        min_silence_duration_ms: 100,
+        speech_pad_ms: 0,
+        ..Default::default()
+    }
+}
+
+/// Probabilities of 32ms windows: two speech bursts around a 128ms dip,
+/// followed by enough silence to close the segment
+fn dip_probs() -> Vec<f32> {
+    let mut probs = vec![0.9, 0.9, 0.2, 0.2, 0.2, 0.2, 0.9, 0.9];
+    probs.extend_from_slice(&[0.1; 10]);
+    probs
+}
+
+#[test]
+fn test_segments_from_probs() {
+    let segments = segment_probabilities(&dip_probs(), &config());
+
+    assert_eq!(segments.len(), 2);
+    assert!((segments[0].start - 0.0).abs() < 1e-6);
+    assert!((segments[0].end - 0.064).abs() < 1e-6);
+    assert!((segments[1].start - 0.192).abs() < 1e-6);
+    assert!((segments[1].end - 0.256).abs() < 1e-6);
+    assert!((segments[0].confidence - 0.9).abs() < 1e-6);
+}
+
+#[test]
+fn test_hangover_bridges_intra_word_dip() {
+    let config = VadConfig {
+        hangover_ms: 150,
+        ..config()
+    };
+    let segments = segment_probabilities(&dip_probs(), &config);
+
+    assert_eq!(segments.len(), 1);
+    assert!((segments[0].start - 0.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_hangover_holds_speech_for_the_configured_time() {
+    // 64ms of speech, then 256ms below the threshold, then speech again
+    let mut probs = vec![0.9, 0.9];
+    probs.extend_from_slice(&[0.2; 8]);
+    probs.extend_from_slice(&[0.9, 0.9]);
+    probs.extend_from_slice(&[0.1; 10]);
+    let held = VadConfig { hangover_ms: 64, ..config() };
+
+    // The two windows after each burst are held as speech, so the segments
+    // end 64ms later, but a pause longer than hangover plus minimum silence
+    // still splits them
+    let segments = segment_probabilities(&probs, &held);
+    assert_eq!(segments.len(), 2);
+    assert!((segments[0].end - 0.128).abs() < 1e-6);
+    assert!((segments[1].start - 0.320).abs() < 1e-6);
+    assert!((segments[1].end - 0.448).abs() < 1e-6);
+
+    let plain = segment_probabilities(&probs, &config());
+    assert!((plain[0].end - 0.064).abs() < 1e-6);
+}
+
+#[test]
+fn test_gap_fill_bridges_dropout() {
+    let config = VadConfig {
+        gap_fill_windows: 4,
+        ..config()
+    };
+    let segments = segment_probabilities(&dip_probs(), &config);
+
+    assert_eq!(segments.len(), 1);
+    assert!((segments[0].end - 0.256).abs() < 1e-6);
+}
+
+#[test]
+fn test_endpoint_transitions() {
+    use EndpointState::*;
+
+    let config = VadConfig {
+        endpoint_silence_ms: 200,
+        ..config()
+    };
+    // A 128ms pause inside the utterance, then 7 silent windows (224ms)
+    let mut probs = vec![0.1, 0.1, 0.9, 0.9, 0.2, 0.2, 0.2, 0.2, 0.9];
+    probs.extend_from_slice(&[0.1; 9]);
+
+    let mut expected = vec![Listening, Listening];
+    expected.extend_from_slice(&[Speaking; 7]);
+    expected.extend_from_slice(&[Speaking; 6]);
+    expected.extend_from_slice(&[Endpointed, Listening, Listening]);
+    assert_eq!(endpoint_from_probs(&probs, &config), expected);
+}
+
+#[test]
+fn test_endpoint_fires_once_per_utterance() {
+    let config = VadConfig {
+        endpoint_silence_ms: 64,
+        ..config()
+    };
+    let probs = [0.9, 0.1, 0.1, 0.1, 0.9, 0.1, 0.1, 0.1];
+
+    let states = endpoint_from_probs(&probs, &config);
+    let endpoints = states.iter().filter(|&&s| s == EndpointState::Endpointed).count();
+    assert_eq!(endpoints, 2);
+}
+
+#[test]
+fn test_ongoing_events_track_growing_segment() {
+    let config = VadConfig {
+        speech_pad_ms: 30,
+        ..config()
+    };
+    let probs = [0.1, 0.9, 0.9, 0.9, 0.1, 0.1, 0.1, 0.1, 0.1];
+    let events = events_from_probs(&probs, &config);
+
+    match events[0] {
+        VadEvent::SpeechStart { timestamp, .. } => assert!((timestamp - 0.032).abs() < 1e-6),
+        other => panic!("expected SpeechStart first, got {:?}", other),
+    }
+    let ends: Vec<f32> = events
+        .iter()
+        .filter_map(|e| match e {
+            VadEvent::SpeechOngoing { start, current_end } => {
+                assert!((start - 0.032).abs() < 1e-6);
+                Some(*current_end)
+            }
+            _ => None,
+        })
+        .collect();
+    // Grows with each speech window, then holds through the trailing silence
+    let expected = [0.096, 0.128, 0.128, 0.128, 0.128];
+    assert_eq!(ends.len(), expected.len());
+    for (end, expected) in ends.iter().zip(expected) {
+        assert!((end - expected).abs() < 1e-6);
+    }
+    match events.last() {
+        Some(VadEvent::SpeechEnd { timestamp, .. }) => assert!((timestamp - 0.158).abs() < 1e-6),
+        other => panic!("expected a final SpeechEnd, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_peak_confidence_separates_tapered_from_flat_segments() {
+    let config = config();
+    // Weak edges around one strong window, versus uniformly medium speech
+    let mut tapered = vec![0.55, 0.55, 0.99, 0.55, 0.55];
+    tapered.extend_from_slice(&[0.1; 10]);
+    let mut flat = vec![0.7; 5];
+    flat.extend_from_slice(&[0.1; 10]);
+
+    let tapered = &segment_probabilities(&tapered, &config)[0];
+    let flat = &segment_probabilities(&flat, &config)[0];
+
+    assert!(tapered.confidence < flat.confidence);
+    assert!(tapered.peak_confidence > flat.peak_confidence);
+    assert!((tapered.peak_confidence - 0.99).abs() < 1e-6);
+    assert!((flat.peak_confidence - 0.7).abs() < 1e-6);
+
+    let json = serde_json::to_string(tapered).unwrap();
+    assert!(json.contains("\"peak_confidence\""));
+}
+
+#[test]
+fn test_turn_silence_splits_turns_but_not_word_gaps() {
+    // 64ms word gap, then a 320ms turn gap
+    let mut probs = vec![0.9, 0.9, 0.2, 0.2, 0.9, 0.9];
+    probs.extend_from_slice(&[0.1; 10]);
+    probs.extend_from_slice(&[0.9, 0.9]);
+    probs.extend_from_slice(&[0.1; 40]);
+
+    let bridged = VadConfig {
+        min_silence_duration_ms: 1000,
+        ..config()
+    };
+    assert_eq!(segment_probabilities(&probs, &bridged).len(), 1);
+
+    let turns = VadConfig {
+        turn_silence_ms: 300,
+        ..bridged
+    };
+    let segments = segment_probabilities(&probs, &turns);
+    assert_eq!(segments.len(), 2);
+    assert!((segments[0].start - 0.0).abs() < 1e-6);
+    assert!((segments[0].end - 0.192).abs() < 1e-6);
+    assert!((segments[1].start - 0.512).abs() < 1e-6);
+}
+
+#[test]
+fn test_time_scale_maps_segments_to_source_timeline() {
+    let plain = segment_probabilities(&dip_probs(), &config());
+    let scaled = segment_probabilities(&dip_probs(), &VadConfig { time_scale: 2.0, ..config() });
+
+    assert_eq!(scaled.len(), plain.len());
+    for (scaled, plain) in scaled.iter().zip(&plain) {
+        assert!((scaled.start - 2.0 * plain.start).abs() < 1e-6);
+        assert!((scaled.end - 2.0 * plain.end).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_running_stats_accumulate_over_windows() {
+    let probs = dip_probs();
+
+    // After the first burst and the dip that closes it
+    let stats = stats_from_probs(&probs[..6], &config());
+    assert_eq!(stats.segment_count, 1);
+    assert!((stats.speech_duration_s - 0.064).abs() < 1e-6);
+    assert!((stats.silence_duration_s - 0.128).abs() < 1e-6);
+
+    let stats = stats_from_probs(&probs, &config());
+    assert_eq!(stats.segment_count, 2);
+    assert!((stats.speech_duration_s - 0.128).abs() < 1e-6);
+    assert!((stats.silence_duration_s - 0.448).abs() < 1e-6);
+    assert!((stats.speech_ratio() - 0.128 / 0.576).abs() < 1e-6);
+
+    assert_eq!(stats_from_probs(&[], &config()).speech_ratio(), 0.0);
+}
+
+/// Score consecutive 512-sample windows starting at `first` by the fraction
+/// of each window covered by `speech`
+fn overlap_probs(speech: std::ops::Range<usize>, len: usize, first: usize) -> Vec<f32> {
+    (first..)
+        .step_by(512)
+        .take_while(|start| start + 512 <= len)
+        .map(|start| {
+            let overlap = (start + 512).min(speech.end).saturating_sub(start.max(speech.start));
+            overlap as f32 / 512.0
+        })
+        .collect()
+}
+
+fn onset_variance(errors: &[f32]) -> f32 {
+    let mean = errors.iter().sum::<f32>() / errors.len() as f32;
+    errors.iter().map(|e| (e - mean).powi(2)).sum::<f32>() / errors.len() as f32
+}
+
+#[test]
+fn test_half_window_refinement_reduces_onset_jitter() {
+    let len = 48000;
+    let mut single = Vec::new();
+    let mut refined = Vec::new();
+
+    for onset in (8000..8512).step_by(32) {
+        let speech = onset..onset + 8000;
+        let aligned = overlap_probs(speech.clone(), len, 0);
+        let offset = overlap_probs(speech, len, 256);
+
+        let segments = segment_probabilities(&aligned, &config());
+        assert_eq!(segments.len(), 1);
+        single.push(segments[0].start * 16000.0 - onset as f32);
+
+        let segments = segment_from_offset_passes(&aligned, &offset, &config());
+        assert_eq!(segments.len(), 1);
+        refined.push(segments[0].start * 16000.0 - onset as f32);
+    }
+
+    assert!(refined.iter().all(|e| e.abs() <= 256.0 + 1e-3));
+    assert!(onset_variance(&refined) < onset_variance(&single) / 2.0);
+}
+
+#[test]
+fn test_initial_skip_ignores_connection_click() {
+    // A click in the first window, then silence and a real utterance at 0.64s
+    let mut probs = vec![0.97, 0.3];
+    probs.extend_from_slice(&[0.05; 18]);
+    probs.extend_from_slice(&[0.9; 5]);
+    probs.extend_from_slice(&[0.05; 10]);
+
+    assert_eq!(segment_probabilities(&probs, &config()).len(), 2);
+
+    let skipping = VadConfig { initial_skip_ms: 50, ..config() };
+    let segments = segment_probabilities(&probs, &skipping);
+    assert_eq!(segments.len(), 1);
+    assert!((segments[0].start - 0.64).abs() < 1e-6);
+    assert_eq!(endpoint_from_probs(&probs[..2], &skipping), vec![EndpointState::Listening; 2]);
+}
+
+#[test]
+fn test_pause_free_speech_over_max_is_flagged_not_split() {
+    // 3.2s of uninterrupted speech, a pause, then a short utterance
+    let mut probs = vec![0.9; 100];
+    probs.extend_from_slice(&[0.1; 10]);
+    probs.extend_from_slice(&[0.9; 10]);
+    probs.extend_from_slice(&[0.1; 10]);
+    let config = VadConfig {
+        max_speech_samples: Some(16000),
+        keep_long_segments: true,
+        ..config()
+    };
+
+    let segments = segment_probabilities(&probs, &config);
+
+    assert_eq!(segments.len(), 2);
+    assert!((segments[0].end - segments[0].start - 3.2).abs() < 1e-4);
+    assert_eq!(segments[0].split_reason, SplitReason::MaxDuration);
+    assert_eq!(segments[1].split_reason, SplitReason::Silence);
+
+    // Without keep_long_segments the long segment is dropped whole
+    let dropping = VadConfig { keep_long_segments: false, ..config };
+    let segments = segment_probabilities(&probs, &dropping);
+    assert_eq!(segments.len(), 1);
+    assert!((segments[0].start - 110.0 * 0.032).abs() < 1e-4);
+}
+
+#[test]
+fn test_bidirectional_pass_sharpens_late_offset() {
+    // Speech covers windows 10..30; the forward pass lingers until window 35
+    // and the reversed pass, lagging the other way, rises from window 5
+    let forward: Vec<f32> = (0..50)
+        .map(|i| if (10..35).contains(&i) { 0.8 } else { 0.1 })
+        .collect();
+    let backward: Vec<f32> = (0..50)
+        .rev()
+        .map(|i| if (5..30).contains(&i) { 0.8 } else { 0.1 })
+        .collect();
+    let true_end = 30.0 * 0.032;
+
+    let forward_only = segment_probabilities(&forward, &config());
+    let combined = segment_from_bidirectional(&forward, &backward, &config());
+
+    assert_eq!(forward_only.len(), 1);
+    assert_eq!(combined.len(), 1);
+    assert!((combined[0].end - true_end).abs() < (forward_only[0].end - true_end).abs());
+    assert!((combined[0].end - true_end).abs() < 1e-4);
+    assert!((combined[0].start - forward_only[0].start).abs() < 1e-4);
+}
+
+/// A 2-window blip, a pause, a 20-window utterance and closing silence
+fn blip_and_utterance() -> Vec<f32> {
+    let mut probs = vec![0.9; 2];
+    probs.extend_from_slice(&[0.1; 10]);
+    probs.extend_from_slice(&[0.9; 20]);
+    probs.extend_from_slice(&[0.1; 10]);
+    probs
+}
+
+#[test]
+fn test_segment_probabilities_without_limits_keeps_every_segment() {
+    let probs = blip_and_utterance();
+
+    let segments = segment_probabilities(&probs, &config());
+
+    let bounds: Vec<_> = segments.iter().map(|ts| (ts.start_sample, ts.end_sample)).collect();
+    assert_eq!(bounds, vec![(0, 1024), (6144, 16384)]);
+    assert!(segment_probabilities(&[], &config()).is_empty());
+}
+
+#[test]
+fn test_segment_probabilities_applies_length_limits() {
+    let probs = blip_and_utterance();
+
+    // 0.064s blip is dropped, the 0.64s utterance is kept
+    let min = VadConfig { min_speech_samples: Some(4000), ..config() };
+    let segments = segment_probabilities(&probs, &min);
+    assert_eq!(segments.len(), 1);
+    assert!((segments[0].start - 12.0 * 0.032).abs() < 1e-4);
+    assert!((segments[0].end - 32.0 * 0.032).abs() < 1e-4);
+
+    // The utterance exceeds the maximum: dropped, or kept whole and flagged
+    let max = VadConfig { max_speech_samples: Some(8000), ..config() };
+    let segments = segment_probabilities(&probs, &max);
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].split_reason, SplitReason::Silence);
+
+    let keep = VadConfig { keep_long_segments: true, ..max };
+    let segments = segment_probabilities(&probs, &keep);
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[1].split_reason, SplitReason::MaxDuration);
+}
+
+#[test]
+fn test_sample_limits_take_precedence_over_scaled_durations() {
+    let probs = blip_and_utterance();
+
+    // Reported times are scaled, the sample positions are not
+    let scaled = VadConfig { time_scale: 0.7, ..config() };
+    let segments = segment_probabilities(&probs, &scaled);
+    assert_eq!((segments[1].start_sample, segments[1].end_sample), (12 * 512, 32 * 512));
+    assert_eq!(segments[1].sample_len(), 10240);
+
+    // The limits compare sample counts: a segment exactly at both bounds is
+    // kept unflagged, one sample beyond either bound is not
+    let exact = VadConfig {
+        min_speech_samples: Some(10240),
+        max_speech_samples: Some(10240),
+        keep_long_segments: true,
+        ..scaled
+    };
+    let segments = segment_probabilities(&probs, &exact);
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].split_reason, SplitReason::Silence);
+
+    let shorter_max = VadConfig { max_speech_samples: Some(10239), ..exact.clone() };
+    let segments = segment_probabilities(&probs, &shorter_max);
+    assert_eq!(segments[0].split_reason, SplitReason::MaxDuration);
+
+    let longer_min = VadConfig { min_speech_samples: Some(10241), ..exact };
+    assert!(segment_probabilities(&probs, &longer_min).is_empty());
+}
+
+#[test]
+fn test_segment_probabilities_pads_and_merges() {
+    let probs = dip_probs();
+
+    // With enough minimum silence the dip no longer splits the segment
+    let merged = VadConfig { min_silence_duration_ms: 200, ..config() };
+    assert_eq!(segment_probabilities(&probs, &merged).len(), 1);
+
+    let padded = VadConfig { speech_pad_ms: 32, ..config() };
+    let segments = segment_probabilities(&probs, &padded);
+    assert_eq!(segments.len(), 2);
+    assert!((segments[1].end - (0.256 + 0.032)).abs() < 1e-4);
+}
+
+#[test]
+fn test_suggested_parameters_follow_the_probability_track() {
+    // A recording the model is unsure about: 512ms bursts at 0.4 separated
+    // by 384ms pauses
+    let mut probs = Vec::new();
+    for _ in 0..8 {
+        probs.extend_from_slice(&[0.05; 12]);
+        probs.extend_from_slice(&[0.4; 16]);
+    }
+    probs.extend_from_slice(&[0.05; 12]);
+    assert!(segment_probabilities(&probs, &config()).is_empty());
+
+    let suggested = suggest_parameters_from_probs(&probs, &config());
+    let threshold = suggested.config.threshold;
+    assert!(threshold > 0.05 && threshold < 0.4);
+    assert_eq!(suggested.config.min_silence_duration_ms, 190);
+    assert_eq!(suggested.min_speech_duration_ms, 256);
+    assert_eq!(suggested.config.min_speech_samples, None);
+    assert_eq!(segment_probabilities(&probs, &suggested.config).len(), 8);
+
+    let unchanged = suggest_parameters_from_probs(&[0.9], &config());
+    assert_eq!(unchanged.config, config());
+    assert_eq!(unchanged.min_speech_duration_ms, 250);
+}
+
+#[test]
+fn test_streaming_counterparts_agree_on_gap_filling() {
+    let filling = VadConfig { gap_fill_windows: 4, ..config() };
+    let probs = dip_probs();
+
+    // Streaming never fills gaps, so the dip counts as silence everywhere
+    let stats = stats_from_probs(&probs, &filling);
+    assert!((stats.speech_duration_s - 4.0 * 0.032).abs() < 1e-4);
+    assert_eq!(events_from_probs(&probs, &filling), events_from_probs(&probs, &config()));
+    assert_eq!(endpoint_from_probs(&probs, &filling), endpoint_from_probs(&probs, &config()));
+    assert_eq!(stats, stats_from_probs(&probs, &config()));
+}
@@ -0,0 +1,86 @@
+use ndarray::Array1;
+use silero_vad_rs::{Error, SileroVAD, VADIterator, VadConfig};
+use tempfile::TempDir;
+
+fn vad(temp_dir: &TempDir, min_audio_windows: usize) -> VADIterator {
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let config = VadConfig { min_audio_windows, ..Default::default() };
+    VADIterator::from_config(model, config).unwrap()
+}
+
+#[test]
+fn test_short_audio_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::<f32>::zeros(512 * 2);
+
+    let result = vad(&temp_dir, 4).get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0);
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
+
+#[test]
+fn test_short_audio_check_can_be_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::<f32>::zeros(512 * 2);
+
+    let result = vad(&temp_dir, 0).get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0);
+    assert!(result.unwrap().is_empty());
+}
+
+#[test]
+fn test_short_audio_accepted_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let mut detector = VADIterator::new(model, 0.5, 16000, 100, 30).unwrap();
+    let audio = Array1::<f32>::zeros(512 * 2);
+
+    assert_eq!(VadConfig::default().min_audio_windows, 0);
+    let result = detector.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0);
+    assert!(result.unwrap().is_empty());
+}
+
+#[test]
+fn test_sub_window_audio_is_padded_to_one_window() {
+    let temp_dir = TempDir::new().unwrap();
+
+    for len in [1, 300] {
+        let audio = Array1::<f32>::zeros(len);
+        let mut detector = vad(&temp_dir, 0);
+        let result = detector.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0);
+
+        assert!(result.unwrap().len() <= 1);
+        assert_eq!(detector.processed_samples(), len);
+    }
+}
+
+#[test]
+fn test_sub_window_speech_ends_within_the_clip() {
+    let temp_dir = TempDir::new().unwrap();
+    let model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    // Every window counts as speech at a zero threshold, so a segment is
+    // always produced and its padded end has to be clamped to the clip
+    let mut detector = VADIterator::new(model, 0.0, 16000, 100, 30).unwrap();
+    let len = 300;
+    let audio = Array1::from_shape_fn(len, |i| {
+        let t = i as f32 / 16000.0;
+        0.5 * (2.0 * std::f32::consts::PI * 220.0 * t).sin()
+            + 0.25 * (2.0 * std::f32::consts::PI * 440.0 * t).sin()
+    });
+
+    let segments = detector.get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 30).unwrap();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].start, 0.0);
+    assert!(segments[0].end <= len as f32 / 16000.0 + 1e-6);
+    assert_eq!(segments[0].end_sample, len);
+    // Only the real samples count as speech, not the padding
+    let speech = detector.running_stats().speech_duration_s;
+    assert!((speech - len as f32 / 16000.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_empty_audio_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let audio = Array1::<f32>::zeros(0);
+
+    let result = vad(&temp_dir, 0).get_speech_timestamps(&audio.view(), 0, f32::INFINITY, 0, 0);
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
@@ -0,0 +1,38 @@
+use silero_vad_rs::{SileroVAD, TimingStats};
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_timing_stats_summary() {
+    // 1ms..=100ms in shuffled order
+    let durations: Vec<Duration> = (1..=100u64)
+        .map(|i| Duration::from_millis((i * 37) % 100 + 1))
+        .collect();
+
+    let stats = TimingStats::from_durations(&durations).unwrap();
+    assert_eq!(stats.count, 100);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.max, Duration::from_millis(100));
+    assert_eq!(stats.p99, Duration::from_millis(99));
+    assert_eq!(stats.mean, Duration::from_micros(50_500));
+}
+
+#[test]
+fn test_timing_stats_empty() {
+    assert!(TimingStats::from_durations(&[]).is_none());
+}
+
+#[test]
+fn test_processing_time_estimate_scales_with_length() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut model = SileroVAD::new(&temp_dir.path().join("silero_vad.onnx")).unwrap();
+    let context = model.context().to_owned();
+
+    let one = model.estimate_processing_time(512, 16000).unwrap();
+    let hundred = model.estimate_processing_time(512 * 100, 16000).unwrap();
+
+    assert!(one > Duration::ZERO);
+    assert_eq!(hundred, one * 100);
+    assert_eq!(model.context(), context);
+    assert!(model.estimate_processing_time(512, 8000).is_err());
+}
@@ -0,0 +1,540 @@
+use silero_vad_rs::utils::{
+    apply_fades, chunk_count, clipping_ratio, collect_chunks, collect_chunks_with,
+    collect_chunks_with_context, concat_segments_with_gaps, convert_sample_index,
+    convert_sample_range, diff_segments, downmix_channels, downmix_planar, duration_histogram,
+    estimate_snr, filter_by_confidence, gate_silence, gate_silence_with_fade, group_by_confidence,
+    i32_to_mono_f32, invert_segments, overlap_segments, quantize_segments, read_audio, read_raw_f32,
+    rechunk_segments, rle_decode, rle_encode, rms_envelope, sample_range, save_audio,
+    save_audio_opts, save_probability_track, snap_to_zero_crossings, soft_limit, suggest_parameters,
+    suppress_clipped_onsets, timestamps_to_textgrid, ChannelMode, OverlapPolicy, Precision,
+    WriteOptions,
+};
+use ndarray::{array, Array1};
+use silero_vad_rs::{SpeechTimestamps, SplitReason};
+
+fn ts(start: f32, end: f32) -> SpeechTimestamps {
+    SpeechTimestamps::new(start, end)
+}
+
+fn ts_conf(start: f32, end: f32, confidence: f32) -> SpeechTimestamps {
+    SpeechTimestamps { confidence, peak_confidence: confidence, ..SpeechTimestamps::new(start, end) }
+}
+
+#[test]
+fn test_rechunk_merges_short_segments() {
+    let segments = vec![ts(0.0, 4.0), ts(5.0, 9.0), ts(10.0, 14.0), ts(20.0, 22.0)];
+    let windows = rechunk_segments(&segments, 10.0, 15.0);
+
+    assert_eq!(windows.len(), 2);
+    assert_eq!((windows[0].start, windows[0].end), (0.0, 14.0));
+    assert_eq!((windows[1].start, windows[1].end), (20.0, 22.0));
+}
+
+#[test]
+fn test_rechunk_splits_long_segment() {
+    let windows = rechunk_segments(&[ts(0.0, 45.0)], 10.0, 20.0);
+
+    assert_eq!(windows.len(), 3);
+    for w in &windows {
+        assert!(w.end - w.start <= 20.0 + 1e-4);
+    }
+    assert_eq!(windows[0].start, 0.0);
+    assert_eq!(windows[2].end, 45.0);
+    let reasons: Vec<_> = windows.iter().map(|w| w.split_reason).collect();
+    assert_eq!(
+        reasons,
+        vec![SplitReason::MaxDuration, SplitReason::MaxDuration, SplitReason::Silence]
+    );
+}
+
+#[test]
+fn test_invert_segments_includes_edges() {
+    let gaps = invert_segments(&[ts(1.0, 2.0), ts(3.0, 4.5)], 6.0);
+
+    let bounds: Vec<_> = gaps.iter().map(|g| (g.start, g.end)).collect();
+    assert_eq!(bounds, vec![(0.0, 1.0), (2.0, 3.0), (4.5, 6.0)]);
+}
+
+#[test]
+fn test_invert_segments_touching_boundaries() {
+    let gaps = invert_segments(&[ts(0.0, 2.0), ts(2.0, 3.0), ts(4.0, 6.0)], 6.0);
+
+    let bounds: Vec<_> = gaps.iter().map(|g| (g.start, g.end)).collect();
+    assert_eq!(bounds, vec![(3.0, 4.0)]);
+}
+
+#[test]
+fn test_filter_by_confidence() {
+    let segments = vec![ts_conf(0.0, 1.0, 0.9), ts_conf(2.0, 3.0, 0.55), ts_conf(4.0, 5.0, 0.7)];
+    let kept = filter_by_confidence(&segments, 0.7);
+
+    let starts: Vec<_> = kept.iter().map(|s| s.start).collect();
+    assert_eq!(starts, vec![0.0, 4.0]);
+}
+
+#[test]
+fn test_precision_formatting() {
+    assert_eq!(Precision::Decimals(2).format(1.23456), "1.23");
+    assert_eq!(Precision::Decimals(0).format(2.5001), "3");
+    assert_eq!(Precision::Milliseconds(40).format(1.234), "1.24");
+    assert_eq!(Precision::Milliseconds(1000).format(1.6), "2");
+}
+
+#[test]
+fn test_textgrid_times_are_rounded_with_precision() {
+    let grid = timestamps_to_textgrid(&[ts(0.123_456, 1.0)], 2.000_4);
+
+    assert!(grid.contains("xmax = 0.123 \n"));
+    assert!(grid.contains("xmin = 0.123 \n"));
+    assert!(grid.contains("xmax = 2 \n"));
+    assert!(!grid.contains("0.1234"));
+}
+
+#[test]
+fn test_chunk_count() {
+    assert_eq!(chunk_count(512 * 10, 512, 512), 10);
+    assert_eq!(chunk_count(512 * 10 + 100, 512, 512), 10);
+    assert_eq!(chunk_count(511, 512, 512), 0);
+    assert_eq!(chunk_count(1024, 512, 256), 3);
+}
+
+#[test]
+fn test_apply_fades_shrinks_long_fades() {
+    // 10 samples at 1kHz with 8ms + 12ms of fades: shrunk to 4 + 6 samples
+    let mut audio = Array1::from_elem(10, 1.0f32);
+    apply_fades(&mut audio, 8, 12, 1000);
+
+    assert_eq!(audio[0], 0.0);
+    assert_eq!(audio[9], 0.0);
+    assert!(audio.iter().all(|&v| (0.0..=1.0).contains(&v)));
+    assert!(audio[3] > audio[1]);
+    assert!(audio[5] > audio[8]);
+}
+
+#[test]
+fn test_i32_to_mono_f32_24_bit() {
+    let samples = [8_388_607, -8_388_608, 4_194_304, 0];
+    let audio = i32_to_mono_f32(&samples, 1, 24).unwrap();
+
+    assert!((audio[0] - 1.0).abs() < 1e-6);
+    assert_eq!(audio[1], -1.0);
+    assert_eq!(audio[2], 0.5);
+    assert_eq!(audio[3], 0.0);
+}
+
+#[test]
+fn test_i32_to_mono_f32_downmixes_stereo() {
+    let audio = i32_to_mono_f32(&[4_194_304, 0, -8_388_608, 8_388_608], 2, 24).unwrap();
+
+    assert_eq!(audio.len(), 2);
+    assert_eq!(audio[0], 0.25);
+    assert_eq!(audio[1], 0.0);
+}
+
+#[test]
+fn test_overlap_segments_intersects_channels() {
+    let left = vec![ts(0.0, 3.0), ts(5.0, 8.0)];
+    let right = vec![ts(2.0, 6.0), ts(8.0, 9.0)];
+    let overlaps = overlap_segments(&[left, right]);
+
+    let bounds: Vec<_> = overlaps.iter().map(|o| (o.start, o.end)).collect();
+    assert_eq!(bounds, vec![(2.0, 3.0), (5.0, 6.0)]);
+}
+
+#[test]
+fn test_overlap_segments_ignores_same_channel_overlap() {
+    let overlaps = overlap_segments(&[vec![ts(0.0, 2.0), ts(1.0, 3.0)], vec![]]);
+
+    assert!(overlaps.is_empty());
+}
+
+#[test]
+fn test_quantize_segments_snaps_outwards_and_merges() {
+    let segments = vec![ts(0.05, 0.21), ts(0.23, 0.30), ts(0.50, 0.60), ts(1.01, 1.02)];
+    let snapped = quantize_segments(&segments, 40);
+
+    assert_eq!(snapped.len(), 3);
+    assert!((snapped[0].start - 0.04).abs() < 1e-5 && (snapped[0].end - 0.32).abs() < 1e-5);
+    // Boundaries already on the grid stay put
+    assert!((snapped[1].start - 0.48).abs() < 1e-5 && (snapped[1].end - 0.60).abs() < 1e-5);
+    assert!((snapped[2].start - 1.00).abs() < 1e-5 && (snapped[2].end - 1.04).abs() < 1e-5);
+}
+
+fn read_i16(path: &std::path::Path) -> Vec<i16> {
+    hound::WavReader::open(path).unwrap().samples::<i16>().map(|s| s.unwrap()).collect()
+}
+
+#[test]
+fn test_save_audio_dither_changes_lsbs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    // A third of an LSB: truncation silences it completely
+    let audio = Array1::from_elem(16000, 0.3 / 32768.0);
+
+    let plain = dir.path().join("plain.wav");
+    save_audio(&plain, &audio, 16000).unwrap();
+    assert!(read_i16(&plain).iter().all(|&s| s == 0));
+
+    let dithered = dir.path().join("dithered.wav");
+    save_audio_opts(&dithered, &audio, 16000, WriteOptions { dither: true }).unwrap();
+    let samples = read_i16(&dithered);
+    assert!(samples.iter().all(|&s| (-1..=2).contains(&s)));
+    assert!(samples.iter().any(|&s| s != 0));
+    // Dither preserves the level on average
+    let mean = samples.iter().map(|&s| s as f32).sum::<f32>() / samples.len() as f32;
+    assert!((mean - 0.3).abs() < 0.05);
+}
+
+#[test]
+fn test_rms_envelope_aligns_with_vad_windows() {
+    // A silent window, a full-scale square wave, a half-scale constant and a partial window
+    let mut audio = vec![0.0f32; 512];
+    audio.extend((0..512).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }));
+    audio.extend(std::iter::repeat(0.5).take(512));
+    audio.extend(std::iter::repeat(1.0).take(100));
+
+    let envelope = rms_envelope(&Array1::from_vec(audio), 16000, 512);
+
+    assert_eq!(envelope, vec![(0.0, 0.0), (0.032, 1.0), (0.064, 0.5)]);
+    assert!(rms_envelope(&Array1::zeros(100), 16000, 512).is_empty());
+    assert!(rms_envelope(&Array1::zeros(1024), 16000, 0).is_empty());
+}
+
+#[test]
+fn test_probability_track_holds_each_window_at_the_source_rate() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("probs.wav");
+
+    save_probability_track(&path, &[0.0, 0.5, 1.0, 1.7, -0.3], 16000, 512).unwrap();
+
+    let track = read_audio(&path, 16000).unwrap();
+    assert_eq!(track.len(), 5 * 512);
+    // Out-of-range probabilities are clamped to the 0-1 range
+    for (window, expected) in track.exact_chunks(512).into_iter().zip([0.0, 0.5, 1.0, 1.0, 0.0]) {
+        assert!(window.iter().all(|&v| (v - expected).abs() < 1e-3));
+    }
+}
+
+#[test]
+fn test_read_raw_f32_downmixes_interleaved_frames() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("stereo.raw");
+    let interleaved = [0.5f32, -0.5, 1.0, 0.0, -0.25, -0.75];
+    let bytes: Vec<u8> = interleaved.iter().flat_map(|v| v.to_le_bytes()).collect();
+    std::fs::write(&path, &bytes).unwrap();
+
+    assert_eq!(read_raw_f32(&path, 16000, 1).unwrap().to_vec(), interleaved.to_vec());
+    assert_eq!(read_raw_f32(&path, 16000, 2).unwrap().to_vec(), vec![0.0, 0.5, -0.5]);
+
+    // Six samples are not a whole number of 4-channel frames
+    assert!(matches!(read_raw_f32(&path, 16000, 4), Err(silero_vad_rs::Error::AudioProcessing(_))));
+    assert!(matches!(read_raw_f32(&path, 16000, 0), Err(silero_vad_rs::Error::InvalidInput(_))));
+    assert!(matches!(read_raw_f32(&path, 0, 1), Err(silero_vad_rs::Error::InvalidInput(_))));
+}
+
+#[test]
+fn test_collect_chunks_merges_overlapping_segments() {
+    let audio = Array1::from_shape_fn(100, |i| i as f32);
+    let segments = vec![ts(0.1, 0.4), ts(0.3, 0.6)];
+
+    let merged = collect_chunks(&segments, &audio, 100).unwrap();
+    assert_eq!(merged.to_vec(), (10..60).map(|i| i as f32).collect::<Vec<_>>());
+
+    let rejected = collect_chunks_with(&segments, &audio, 100, OverlapPolicy::Error);
+    assert!(rejected.is_err());
+}
+
+#[test]
+fn test_estimate_snr_from_segments() {
+    // Speech at amplitude 1.0 in the middle, noise at 0.1 elsewhere: 20 dB
+    let audio = Array1::from_shape_fn(300, |i| {
+        let level = if (100..200).contains(&i) { 1.0 } else { 0.1 };
+        if i % 2 == 0 { level } else { -level }
+    });
+    let snr = estimate_snr(&audio, &[ts(1.0, 2.0)], 100).unwrap();
+    assert!((snr - 20.0).abs() < 1e-3);
+
+    assert!(estimate_snr(&audio, &[ts(0.0, 3.0)], 100).is_err());
+    assert!(estimate_snr(&audio, &[], 100).is_err());
+}
+
+#[test]
+fn test_downmix_channels() {
+    let audio = array![[1.0f32, 0.0, -1.0], [0.0, 0.5, 1.0]];
+
+    let mixed = downmix_channels(&audio.view(), ChannelMode::Downmix).unwrap();
+    assert_eq!(mixed.to_vec(), vec![0.5, 0.25, 0.0]);
+    let right = downmix_channels(&audio.view(), ChannelMode::Select(1)).unwrap();
+    assert_eq!(right.to_vec(), vec![0.0, 0.5, 1.0]);
+
+    assert!(downmix_channels(&audio.view(), ChannelMode::Select(2)).is_err());
+    assert!(downmix_channels(&audio.view(), ChannelMode::Keep).is_err());
+}
+
+#[test]
+fn test_downmix_planar() {
+    let mono = downmix_planar(&[1.0, 0.0, -1.0], &[0.0, 0.5, 1.0]).unwrap();
+    assert_eq!(mono.to_vec(), vec![0.5, 0.25, 0.0]);
+
+    assert!(downmix_planar(&[1.0, 0.0], &[0.0]).is_err());
+}
+
+#[test]
+fn test_sample_ranges_across_rates() {
+    let segment = ts(0.1, 0.25);
+    assert_eq!(sample_range(&segment, 8000), 800..2000);
+    assert_eq!(sample_range(&segment, 16000), 1600..4000);
+
+    assert_eq!(convert_sample_range(800..2000, 8000, 16000), 1600..4000);
+    assert_eq!(convert_sample_range(1600..4000, 16000, 8000), 800..2000);
+    assert_eq!(convert_sample_index(3, 16000, 8000), 2);
+}
+
+#[test]
+fn test_soft_limit_tames_transients_only() {
+    let mut chunk = Array1::from_shape_fn(512, |i| 0.2 * (i as f32 / 8.0).sin());
+    chunk[100] = 0.98;
+    chunk[300] = -1.0;
+    let original = chunk.clone();
+
+    soft_limit(&mut chunk, 0.5);
+
+    assert!(chunk[100] < 0.98 && chunk[100] > 0.5);
+    assert!(chunk[300] > -1.0 && chunk[300] < -0.5);
+    for i in (0..512).filter(|&i| i != 100 && i != 300) {
+        assert_eq!(chunk[i], original[i]);
+    }
+}
+
+#[test]
+fn test_group_by_confidence_boundaries() {
+    let segments = vec![
+        ts_conf(0.0, 1.0, 0.3),
+        ts_conf(1.0, 2.0, 0.5),
+        ts_conf(2.0, 3.0, 0.79),
+        ts_conf(3.0, 4.0, 0.8),
+        ts_conf(4.0, 5.0, 0.95),
+    ];
+    let groups = group_by_confidence(&segments, &[0.8, 0.5]);
+
+    let starts: Vec<Vec<f32>> = groups.iter().map(|g| g.iter().map(|ts| ts.start).collect()).collect();
+    assert_eq!(starts, vec![vec![0.0], vec![1.0, 2.0], vec![3.0, 4.0]]);
+
+    assert_eq!(group_by_confidence(&segments, &[]).len(), 1);
+}
+
+#[test]
+fn test_concat_segments_with_gaps() {
+    let audio = Array1::from_elem(16000, 0.5f32);
+    let segments = vec![ts(0.0, 0.1), ts(0.2, 0.25), ts(0.5, 0.8)];
+
+    let joined = concat_segments_with_gaps(&audio, &segments, 16000, 50);
+
+    // 1600 + 800 + 4800 samples of speech plus two 800-sample gaps
+    assert_eq!(joined.len(), 1600 + 800 + 4800 + 2 * 800);
+    assert_eq!(joined[1599], 0.5);
+    assert!(joined.slice(ndarray::s![1600..2400]).iter().all(|&x| x == 0.0));
+    assert_eq!(joined[2400], 0.5);
+}
+
+#[test]
+fn test_rle_round_trip() {
+    let decisions = [false, false, true, true, true, false, true];
+
+    let runs = rle_encode(&decisions);
+
+    assert_eq!(runs, vec![(false, 2), (true, 3), (false, 1), (true, 1)]);
+    assert_eq!(rle_decode(&runs), decisions);
+    assert!(rle_encode(&[]).is_empty());
+}
+
+#[test]
+fn test_suggest_parameters_from_audio_statistics() {
+    // 640ms tone bursts separated by 320ms of faint hum
+    let audio = Array1::from_shape_fn(15360 * 5, |i| {
+        if i % 15360 < 10240 {
+            0.5 * (i as f32 * 0.05).sin()
+        } else {
+            0.001 * (i as f32 * 0.02).sin()
+        }
+    });
+
+    let config = suggest_parameters(&audio.view(), 16000);
+
+    assert_eq!(config.sampling_rate, 16000);
+    assert_eq!(config.threshold, silero_vad_rs::VadConfig::default().threshold);
+    // Half the typical pause, and half the shortest burst capped at 300ms
+    assert_eq!(config.min_silence_duration_ms, 160);
+    assert_eq!(config.min_speech_samples, Some(4800));
+
+    // A steady signal gives no evidence and keeps the defaults
+    let steady = Array1::from_elem(16000, 0.1f32);
+    let unchanged = suggest_parameters(&steady.view(), 16000);
+    assert_eq!(unchanged, silero_vad_rs::VadConfig::default());
+}
+
+#[test]
+fn test_gate_silence_preserves_length() {
+    let audio = Array1::from_elem(16000, 0.5f32);
+    let segments = vec![ts(0.1, 0.2), ts(0.5, 0.6)];
+
+    let gated = gate_silence(&segments, &audio, 16000);
+
+    assert_eq!(gated.len(), audio.len());
+    assert_eq!(gated[1599], 0.0);
+    assert_eq!(gated[1600], 0.5);
+    assert_eq!(gated[3199], 0.5);
+    assert_eq!(gated[3200], 0.0);
+    assert_eq!(gated.iter().filter(|&&x| x != 0.0).count(), 3200);
+
+    let faded = gate_silence_with_fade(&segments, &audio, 16000, 5);
+    assert_eq!(faded.len(), audio.len());
+    assert_eq!(faded[1600], 0.0);
+    assert!(faded[1640] > 0.0 && faded[1640] < 0.5);
+    assert_eq!(faded[2400], 0.5);
+}
+
+#[test]
+fn test_timestamps_to_textgrid() {
+    let grid = timestamps_to_textgrid(&[ts(0.5, 1.0), ts(0.75, 1.25), ts(1.5, 2.5)], 2.5);
+
+    let expected = "File type = \"ooTextFile\"
+Object class = \"TextGrid\"
+
+xmin = 0 
+xmax = 2.5 
+tiers? <exists> 
+size = 1 
+item []: 
+    item [1]:
+        class = \"IntervalTier\" 
+        name = \"speech\" 
+        xmin = 0 
+        xmax = 2.5 
+        intervals: size = 4 
+        intervals [1]:
+            xmin = 0 
+            xmax = 0.5 
+            text = \"\" 
+        intervals [2]:
+            xmin = 0.5 
+            xmax = 1.25 
+            text = \"speech\" 
+        intervals [3]:
+            xmin = 1.25 
+            xmax = 1.5 
+            text = \"\" 
+        intervals [4]:
+            xmin = 1.5 
+            xmax = 2.5 
+            text = \"speech\" 
+";
+    assert_eq!(grid, expected);
+}
+
+#[test]
+fn test_collect_chunks_with_context() {
+    let audio = Array1::from_shape_fn(16000 * 10, |i| i as f32);
+    let segments = vec![ts(1.0, 2.0), ts(2.5, 3.0), ts(7.0, 9.5)];
+
+    let clips = collect_chunks_with_context(&segments, &audio, 16000, 1.0);
+
+    // The first two segments' contexts overlap and merge; the last is clamped
+    assert_eq!(clips.len(), 2);
+    assert_eq!(clips[0].len(), 16000 * 4);
+    assert_eq!(clips[0][0], 0.0);
+    assert_eq!(clips[1].len(), 16000 * 4);
+    assert_eq!(clips[1][0], (16000 * 6) as f32);
+}
+
+#[test]
+fn test_diff_segments() {
+    let a = vec![ts(0.0, 1.0), ts(2.0, 3.0), ts(5.0, 6.0)];
+    let b = vec![ts(0.0, 1.0), ts(2.1, 3.0), ts(8.0, 9.0)];
+
+    let diff = diff_segments(&a, &b);
+
+    assert_eq!(diff.unchanged, 1);
+    assert_eq!(diff.shifted.len(), 1);
+    assert_eq!((diff.shifted[0].0.start, diff.shifted[0].1.start), (2.0, 2.1));
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].start, 5.0);
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].start, 8.0);
+    assert!(diff_segments(&a, &a).is_empty());
+}
+
+#[test]
+fn test_suppress_clipped_onsets() {
+    // Silence with a clipped transient at 0.5s and a clean tone at 1.0s
+    let audio = Array1::from_shape_fn(32000, |i| match i {
+        8000..=8799 if i % 2 == 0 => 1.0,
+        8000..=8799 => -1.0,
+        16000..=23999 => 0.5 * (i as f32 * 0.05).sin(),
+        _ => 0.0,
+    });
+    let segments = vec![ts(0.5, 0.55), ts(1.0, 1.5)];
+
+    assert!(clipping_ratio(&audio.view(), 0.99) > 0.0);
+    let kept = suppress_clipped_onsets(&segments, &audio.view(), 16000);
+
+    assert_eq!(kept.len(), 1);
+    assert_eq!(kept[0].start, 1.0);
+}
+
+#[test]
+fn test_duration_histogram() {
+    let segments = vec![
+        ts(0.0, 0.5),
+        ts(1.0, 2.0),
+        ts(3.0, 4.5),
+        ts(5.0, 7.9),
+        ts(8.0, 20.0),
+        ts(30.0, 33.0),
+    ];
+
+    // Bins [0, 1), [1, 2), [2, 3); 3s and longer land in the last bin
+    assert_eq!(duration_histogram(&segments, 1.0, 3.0), vec![1, 2, 3]);
+    assert_eq!(duration_histogram(&segments, 2.0, 5.0), vec![3, 2, 1]);
+    assert!(duration_histogram(&segments, 0.0, 3.0).is_empty());
+}
+
+/// 200ms at 1kHz with zero crossings at samples 3, 95, 104 and 196
+fn crossing_audio() -> Array1<f32> {
+    Array1::from_shape_fn(200, |i| match i {
+        0..=2 | 95..=103 | 196..=199 => -1.0,
+        _ => 1.0,
+    })
+}
+
+#[test]
+fn test_snap_to_zero_crossings_picks_the_nearest_side() {
+    let audio = crossing_audio();
+
+    // 100 is 4 samples before the crossing at 104 and 5 after the one at 95;
+    // 97 is 2 samples after 95 and 7 before 104
+    let mut segments = vec![ts(0.100, 0.150), ts(0.050, 0.097)];
+    snap_to_zero_crossings(&mut segments, &audio, 1000, 10);
+
+    assert!((segments[0].start - 0.104).abs() < 1e-6);
+    assert!((segments[1].end - 0.095).abs() < 1e-6);
+    // No crossing within 10ms of 150 or 50: left unchanged
+    assert_eq!(segments[0].end, 0.150);
+    assert_eq!(segments[1].start, 0.050);
+}
+
+#[test]
+fn test_snap_to_zero_crossings_clamps_at_the_buffer_edges() {
+    let audio = crossing_audio();
+
+    // The search is cut off at the first and last sample instead of
+    // wrapping or reading out of bounds
+    let mut segments = vec![ts(0.0, 0.200)];
+    snap_to_zero_crossings(&mut segments, &audio, 1000, 10);
+    assert!((segments[0].start - 0.003).abs() < 1e-6);
+    assert!((segments[0].end - 0.196).abs() < 1e-6);
+
+    // A boundary past the end of the audio is left alone
+    let mut beyond = vec![ts(0.100, 0.500)];
+    snap_to_zero_crossings(&mut beyond, &audio, 1000, 10);
+    assert_eq!(beyond[0].end, 0.500);
+}